@@ -0,0 +1,134 @@
+// © 2023 costott. All rights reserved. 
+// This code is provided for viewing purposes only. Copying, reproduction, 
+// or distribution of this code, in whole or in part, in any form or by any 
+// means, is strictly prohibited without prior written permission from the 
+// copyright owner.
+
+use super::complex::*;
+
+/// how big the dropped dz^2 term can be relative to the rest of an iteration
+/// (the precision of a double, so skipping doesn't change the output)
+const BLA_EPSILON: f64 = f64::EPSILON / 2.;
+
+#[derive(Clone, Copy)]
+/// bilinear approximation of some perturbed iterations,
+/// taking dz to a*dz + b*dc while |dz| is within the radius
+pub struct BlaStep {
+    a: Complex,
+    b: Complex,
+    /// number of iterations the step skips
+    iterations: usize,
+    radius2: f64
+}
+impl BlaStep {
+    /// approximation of a single z^2 + c iteration from the reference z,
+    /// where b is how the iteration changes with c
+    fn single(z: Complex, b: Complex) -> BlaStep {
+        let radius = BLA_EPSILON * z.abs_squared().sqrt();
+        BlaStep { a: z * 2., b, iterations: 1, radius2: radius * radius }
+    }
+
+    /// combines step x followed by step y into one step,
+    /// only valid for as long as both steps are for every dc up to max_dc
+    fn merge(x: &BlaStep, y: &BlaStep, max_dc: f64) -> BlaStep {
+        let x_a = x.a.abs_squared().sqrt();
+        let x_b = x.b.abs_squared().sqrt();
+        // the radius of y has to be reached after going through x
+        let y_radius = ((y.radius2.sqrt() - x_b * max_dc) / x_a).max(0.);
+        let radius = x.radius2.sqrt().min(y_radius);
+
+        BlaStep {
+            a: y.a * x.a,
+            b: y.a * x.b + y.b,
+            iterations: x.iterations + y.iterations,
+            radius2: radius * radius
+        }
+    }
+
+    pub fn apply(&self, dz: Complex, dc: Complex) -> Complex {
+        self.a * dz + self.b * dc
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+/// bilinear approximations along a reference orbit, letting pixels skip
+/// many iterations at once while their difference from the reference is small
+///
+/// level k holds steps of 2^k iterations, starting at every multiple of 2^k
+pub struct BlaTable {
+    levels: Vec<Vec<BlaStep>>
+}
+impl BlaTable {
+    /// # Params
+    /// `b`: how an iteration changes with c
+    /// (1 for the mandelbrot set, 0 for julia sets)
+    ///
+    /// `max_dc`: the furthest a pixel is from the reference
+    pub fn new(ref_z: &[Complex], max_ref_iteration: usize, b: Complex, max_dc: f64) -> BlaTable {
+        // steps can't go past the last reference iteration
+        let mut levels = vec![
+            ref_z[..max_ref_iteration].iter().map(|z| BlaStep::single(*z, b)).collect::<Vec<BlaStep>>()
+        ];
+
+        while levels[levels.len()-1].len() > 1 {
+            let next = levels[levels.len()-1].chunks_exact(2)
+                .map(|pair| BlaStep::merge(&pair[0], &pair[1], max_dc))
+                .collect();
+            levels.push(next);
+        }
+
+        BlaTable { levels }
+    }
+
+    /// returns the longest step from the reference iteration which is valid for dz,
+    /// skipping at most max_iterations
+    pub fn lookup(&self, ref_iteration: usize, dz: Complex, max_iterations: usize) -> Option<&BlaStep> {
+        let dz2 = dz.abs_squared();
+        for (level, steps) in self.levels.iter().enumerate().rev() {
+            if ref_iteration & ((1 << level) - 1) != 0 { continue }
+
+            match steps.get(ref_iteration >> level) {
+                Some(step) if step.iterations <= max_iterations && dz2 < step.radius2 => return Some(step),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_matches_iteration() {
+        let c = Complex::new(-0.2, 0.3);
+        let dc = Complex::new(1e-30, -1e-30);
+
+        let mut ref_z = vec![Complex::new(0., 0.)];
+        for _ in 0..100 {
+            let z = ref_z[ref_z.len()-1];
+            ref_z.push(z.square() + c);
+        }
+        let table = BlaTable::new(&ref_z, 100, Complex::new(1., 0.), 2e-30);
+
+        // start after the first iteration, as nothing can be skipped from 0
+        let mut dz = dc;
+        let mut iterated = dc;
+        for i in 1..65 {
+            iterated = ref_z[i] * iterated * 2. + iterated.square() + dc;
+        }
+
+        let mut ref_iteration = 1;
+        while ref_iteration < 65 {
+            let step = table.lookup(ref_iteration, dz, 65 - ref_iteration).unwrap();
+            dz = step.apply(dz, dc);
+            ref_iteration += step.iterations();
+        }
+        assert!((dz - iterated).abs_squared() < 1e-20 * iterated.abs_squared());
+        assert!(table.lookup(64, dc, 100).unwrap().iterations() > 1);
+    }
+}
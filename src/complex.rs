@@ -5,23 +5,23 @@
 // copyright owner.
 
 use std::ops::{Add, Mul, Div, Sub, Neg};
+use std::f64::consts::LOG2_10;
 use dashu_float::{FBig, round::mode};
 
 use crate::{lerpf64_pow, lerp_fbig_pow};
-
-fn factorial(n: u32) -> u32 {
-    let mut result = 1;
-    for i in 2..n+1 {
-        result *= i;
-    }
-    result
-}
-
-fn choose(n: u32, r: u32) -> u32 {
-    assert!(n >= r);
-    if r == 0 || r == n {return 1}
-    if r == 1 || r == n-1 {return n}
-    factorial(n) / (factorial(r)*factorial(n-r))
+use crate::floatexp::ComplexExp;
+use crate::doubledouble::*;
+
+/// the fewest bits a parsed decimal is stored with
+/// (so short decimals, like 0.1, are as precise as a double-double)
+const MIN_DECIMAL_BITS: usize = 128;
+
+/// parses a base 10 number into base 2, 
+/// keeping enough bits for every digit given
+pub fn parse_decimal(number: &str) -> Option<FBig> {
+    let decimal = FBig::<mode::Zero, 10>::from_str_native(number).ok()?;
+    let bits = ((decimal.precision() as f64 * LOG2_10).ceil() as usize).max(MIN_DECIMAL_BITS);
+    Some(decimal.with_base_and_precision::<2>(bits).value())
 }
 
 pub trait ComplexNumber {
@@ -29,6 +29,10 @@ pub trait ComplexNumber {
     /// the absolute value of the complex number, squared
     fn abs_squared(&self) -> f64;
     fn conjugate(&self) -> Self;
+    /// the complex number with its real part made positive
+    fn abs_real(&self) -> Self;
+    /// the complex number with its imaginary part made positive
+    fn abs_im(&self) -> Self;
     fn arg(&self) -> f64;
     /// the squared distance between the complex number and another
     fn distance2_to(&self, other: ComplexType) -> f64;
@@ -39,6 +43,7 @@ pub trait ComplexNumber {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ComplexType {
     Double(Complex),
+    DoubleDouble(ComplexDD),
     Big(BigComplex)
 }
 impl ComplexType {
@@ -46,6 +51,7 @@ impl ComplexType {
     pub fn same_type(real: f64, im: f64, other: ComplexType) -> ComplexType {
         match other {
             ComplexType::Double(_) => ComplexType::Double(Complex::new(real, im)),
+            ComplexType::DoubleDouble(_) => ComplexType::DoubleDouble(ComplexDD::from_complex(Complex::new(real, im))),
             ComplexType::Big(_) => ComplexType::Big(BigComplex::from_f64s(real, im))
         }
     }
@@ -54,6 +60,7 @@ impl ComplexType {
     pub fn real_f64(&self) -> f64 {
         match self {
             ComplexType::Double(c) => c.real,
+            ComplexType::DoubleDouble(c) => c.real.to_f64(),
             ComplexType::Big(c) => c.real.to_f64().value()
         }
     }
@@ -62,6 +69,7 @@ impl ComplexType {
     pub fn real_fbig(&self) -> FBig {
         match self {
             ComplexType::Double(c) => FBig::try_from(c.real).unwrap(),
+            ComplexType::DoubleDouble(c) => c.real.to_fbig(),
             ComplexType::Big(c) => c.real.clone()
         }
     }   
@@ -70,6 +78,7 @@ impl ComplexType {
     pub fn real_string(&self) -> String {
         match self {
             ComplexType::Double(c) => c.real.to_string(),
+            ComplexType::DoubleDouble(c) => c.real.to_string(),
             ComplexType::Big(c) => c.real.clone().with_base_and_precision::<10>(c.real.precision()).value().to_string()
         }
     }
@@ -78,6 +87,7 @@ impl ComplexType {
     pub fn im_f64(&self) -> f64 {
         match self {
             ComplexType::Double(c) => c.im,
+            ComplexType::DoubleDouble(c) => c.im.to_f64(),
             ComplexType::Big(c) => c.im.to_f64().value()
         }
     }
@@ -86,6 +96,7 @@ impl ComplexType {
     pub fn im_fbig(&self) -> FBig {
         match self {
             ComplexType::Double(c) => FBig::try_from(c.im).unwrap(),
+            ComplexType::DoubleDouble(c) => c.im.to_fbig(),
             ComplexType::Big(c) => c.im.clone()
         }
     } 
@@ -93,6 +104,7 @@ impl ComplexType {
     pub fn im_string(&self) -> String {
         match self {
             ComplexType::Double(c) => c.im.to_string(),
+            ComplexType::DoubleDouble(c) => c.im.to_string(),
             ComplexType::Big(c) => c.im.clone().with_base_and_precision::<10>(c.im.precision()).value().to_string()
         }
     }
@@ -100,32 +112,55 @@ impl ComplexType {
     /// returns the complex number of other if it's a double
     /// 
     /// # Panics
-    /// The other is a `ComplexType::Big`
+    /// The other isn't a `ComplexType::Double`
     #[allow(unused)]
     fn unpack_double(&self, other: ComplexType) -> Complex {
         match other {
             ComplexType::Double(c) => c,
-            ComplexType::Big(_) => panic!("Can't perform operations on different complex types - attempted to unpack double")
+            ComplexType::DoubleDouble(_) | ComplexType::Big(_) => panic!("Can't perform operations on different complex types - attempted to unpack double")
         }
     }
 
     /// returns the complex number of other if it's big
     /// 
     /// # Panics
-    /// The other is a `ComplexType::Double`
+    /// The other isn't a `ComplexType::Big`
     #[allow(unused)]
     fn unpack_big(&self, other: ComplexType) -> BigComplex {
         match other {
-            ComplexType::Double(_) => panic!("Can't perform operations on different complex types - attempted to unpack big"),
+            ComplexType::Double(_) | ComplexType::DoubleDouble(_) => panic!("Can't perform operations on different complex types - attempted to unpack big"),
             ComplexType::Big(c) => c,
         }
     }
 
-    /// converts the complex type to be big
-    pub fn make_big(&self) -> ComplexType {
+    /// converts the complex type to be big,
+    /// with at least the given bits of precision
+    pub fn make_big(&self, precision: usize) -> ComplexType {
+        match &self {
+            ComplexType::Double(c) => ComplexType::Big(BigComplex::from_complex(*c).with_precision(precision)),
+            ComplexType::DoubleDouble(c) => ComplexType::Big(c.to_big().with_precision(precision)),
+            ComplexType::Big(c) => match c.precision() < precision {
+                true => ComplexType::Big(c.with_precision(precision)),
+                false => self.clone()
+            }
+        }
+    }
+
+    /// rounds big complex numbers to exactly the given bits of precision
+    /// (other types are unchanged)
+    pub fn with_precision(&self, precision: usize) -> ComplexType {
+        match &self {
+            ComplexType::Big(c) => ComplexType::Big(c.with_precision(precision)),
+            _ => self.clone()
+        }
+    }
+
+    /// converts the complex type to be double-double
+    pub fn make_double_double(&self) -> ComplexType {
         match &self {
-            ComplexType::Double(c) => ComplexType::Big(BigComplex::from_complex(*c)),
-            ComplexType::Big(_) => self.clone()
+            ComplexType::Double(c) => ComplexType::DoubleDouble(ComplexDD::from_complex(*c)),
+            ComplexType::Big(c) => ComplexType::DoubleDouble(ComplexDD::from_big(c)),
+            ComplexType::DoubleDouble(_) => self.clone()
         }
     }
 
@@ -133,36 +168,53 @@ impl ComplexType {
     pub fn make_double(&self) -> ComplexType {
         match &self {
             ComplexType::Big(c) => ComplexType::Double(c.to_complex()),
+            ComplexType::DoubleDouble(c) => ComplexType::Double(c.to_complex()),
             ComplexType::Double(_) => self.clone(),
         }
     }
 
-    pub fn update_real_from_string(&mut self, new: String) {
+    /// # Params
+    /// `precision`: the fewest bits of precision a big number is kept to
+    pub fn update_real_from_string(&mut self, new: String, precision: usize) {
         match self {
             ComplexType::Double(ref mut c) => c.update_real_from_string(new.clone()),
+            ComplexType::DoubleDouble(ref mut c) => c.update_real_from_string(new.clone()),
             ComplexType::Big(ref mut c) => c.update_real_from_string(new.clone())
         }
         match self {
-            ComplexType::Big(_) => {},
+            ComplexType::Big(_) => *self = self.make_big(precision),
             ComplexType::Double(c) => {
                 if c.real.to_string() != new {
-                    *self = self.make_big();
-                    self.update_real_from_string(new);
+                    *self = self.make_big(precision);
+                    self.update_real_from_string(new, precision);
+                }
+            },
+            ComplexType::DoubleDouble(c) => {
+                if c.real.to_string() != new {
+                    *self = self.make_big(precision);
+                    self.update_real_from_string(new, precision);
                 }
             }
         }
     }
-    pub fn update_im_from_string(&mut self, new: String) {
+    pub fn update_im_from_string(&mut self, new: String, precision: usize) {
         match self {
             ComplexType::Double(ref mut c) => c.update_im_from_string(new.clone()),
+            ComplexType::DoubleDouble(ref mut c) => c.update_im_from_string(new.clone()),
             ComplexType::Big(ref mut c) => c.update_im_from_string(new.clone())
         }
         match self {
-            ComplexType::Big(_) => {},
+            ComplexType::Big(_) => *self = self.make_big(precision),
             ComplexType::Double(c) => {
                 if c.im.to_string() != new {
-                    *self = self.make_big();
-                    self.update_im_from_string(new);
+                    *self = self.make_big(precision);
+                    self.update_im_from_string(new, precision);
+                }
+            },
+            ComplexType::DoubleDouble(c) => {
+                if c.im.to_string() != new {
+                    *self = self.make_big(precision);
+                    self.update_im_from_string(new, precision);
                 }
             }
         }
@@ -294,24 +346,68 @@ impl Complex {
         Complex { real, im }
     }
 
-    #[allow(unused)]
-    /// raise the complex number to a given power
-    /// TODO: use demoivre's instead
+    /// raise the complex number to a given integer power
+    /// (by repeated squaring)
     pub fn pow(&self, n: u32) -> Self {
-        let (mut real , mut im) = (0., 0.);
-        for i in 0..=n {
-            let b_pow = n-i;
-            let coefficient = choose(n, i) as f64 * self.im.powi(b_pow as i32) * self.real.powi(i as i32);
-            match b_pow % 4 {
-                0 => {real += coefficient},
-                1 => {im += coefficient},
-                2 => {real -= coefficient},
-                3 => {im -= coefficient},
-                _ => {}
+        let mut result = Complex::new(1., 0.);
+        let mut base = *self;
+        let mut n = n;
+        while n > 0 {
+            if n % 2 == 1 {
+                result = result * base;
             }
+            base = base.square();
+            n /= 2;
+        }
+
+        result
+    }
+
+    /// raise the complex number to a given real power,
+    /// using de moivre's theorem for non-integer powers
+    pub fn powf(&self, p: f64) -> Self {
+        if p.fract() == 0. && p >= 0. {
+            return self.pow(p as u32);
         }
 
-        Complex::new( real, im )
+        let modulus = self.abs_squared().powf(p / 2.);
+        let angle = self.arg() * p;
+        Complex::new(modulus * angle.cos(), modulus * angle.sin())
+    }
+
+    /// raise the complex number to a complex power
+    pub fn powc(&self, p: Complex) -> Self {
+        if self.abs_squared() == 0. {
+            return Complex::new(0., 0.);
+        }
+        (self.ln() * p).exp()
+    }
+
+    /// e raised to the complex number
+    pub fn exp(&self) -> Self {
+        let modulus = self.real.exp();
+        Complex::new(modulus * self.im.cos(), modulus * self.im.sin())
+    }
+
+    /// the principal natural logarithm of the complex number
+    pub fn ln(&self) -> Self {
+        Complex::new(self.abs_squared().ln() / 2., self.arg())
+    }
+
+    pub fn sin(&self) -> Self {
+        Complex::new(self.real.sin() * self.im.cosh(), self.real.cos() * self.im.sinh())
+    }
+
+    pub fn cos(&self) -> Self {
+        Complex::new(self.real.cos() * self.im.cosh(), -self.real.sin() * self.im.sinh())
+    }
+
+    pub fn sinh(&self) -> Self {
+        Complex::new(self.real.sinh() * self.im.cos(), self.real.cosh() * self.im.sin())
+    }
+
+    pub fn cosh(&self) -> Self {
+        Complex::new(self.real.cosh() * self.im.cos(), self.real.sinh() * self.im.sin())
     }
 
     /// returns the real part of the number as an f64
@@ -343,6 +439,20 @@ impl ComplexNumber for Complex {
         }
     }
 
+    fn abs_real(&self) -> Complex {
+        Complex {
+            real: self.real.abs(),
+            im: self.im
+        }
+    }
+
+    fn abs_im(&self) -> Complex {
+        Complex {
+            real: self.real,
+            im: self.im.abs()
+        }
+    }
+
     /// the argument of the complex number between [-pi, pi]
     fn arg(&self) -> f64 {
         f64::atan2(self.im, self.real)
@@ -351,6 +461,7 @@ impl ComplexNumber for Complex {
     fn distance2_to(&self, other: ComplexType) -> f64 {
         match other {
             ComplexType::Double(c) => (*self-c).abs_squared(),
+            ComplexType::DoubleDouble(c) => (ComplexDD::from_complex(*self)-c).abs_squared(),
             ComplexType::Big(c) => (BigComplex::from_complex(*self)-c).abs_squared()
         }
     }
@@ -453,8 +564,17 @@ impl BigComplex {
     /// double precision Complex number
     pub fn from_complex(c: Complex) -> BigComplex {
         BigComplex {
-            real: FBig::try_from(c.real).unwrap().with_precision(64).value(),
-            im: FBig::try_from(c.im).unwrap().with_precision(64).value()
+            real: FBig::try_from(c.real).unwrap(),
+            im: FBig::try_from(c.im).unwrap()
+        }
+    }
+
+    /// creates a new BigComplex number from an 
+    /// extended exponent ComplexExp number
+    pub fn from_complex_exp(c: ComplexExp) -> BigComplex {
+        BigComplex {
+            real: c.real.to_fbig(),
+            im: c.im.to_fbig()
         }
     }
 
@@ -468,13 +588,22 @@ impl BigComplex {
     }
 
     pub fn from_string_base10(real: &str, im: &str) -> BigComplex {
-        // BigComplex { 
-        //     real: FBig::from_str_native(real).unwrap().with_precision(100).value(), 
-        //     im: FBig::from_str_native(im).unwrap().with_precision(100).value()
-        // }
         BigComplex {
-            real: FBig::<mode::Zero, 10>::from_str_native(real).unwrap().with_base::<2>().value(),
-            im: FBig::<mode::Zero, 10>::from_str_native(im).unwrap().with_base::<2>().value()
+            real: parse_decimal(real).unwrap(),
+            im: parse_decimal(im).unwrap()
+        }
+    }
+
+    /// the bits of precision the number is stored with
+    pub fn precision(&self) -> usize {
+        self.real.precision().max(self.im.precision())
+    }
+
+    /// the number rounded (or extended) to the given bits of precision
+    pub fn with_precision(&self, precision: usize) -> BigComplex {
+        BigComplex {
+            real: self.real.clone().with_precision(precision).value(),
+            im: self.im.clone().with_precision(precision).value()
         }
     }
 
@@ -487,6 +616,38 @@ impl BigComplex {
         }
     }
 
+    /// raise the complex number to a given integer power
+    /// (by repeated squaring)
+    pub fn pow(&self, n: u32) -> Self {
+        let mut result = BigComplex::new(FBig::ONE, FBig::ZERO);
+        let mut base = self.clone();
+        let mut n = n;
+        while n > 0 {
+            if n % 2 == 1 {
+                result = &result * &base;
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.square();
+            }
+        }
+
+        result
+    }
+
+    /// raise the complex number to a given real power
+    /// 
+    /// non-integer powers have no arbitrary precision trig, so are 
+    /// calculated in double precision, losing all precision past a double
+    /// (fractals with them are rendered in doubles, see `Fractal::has_fractional_power`)
+    pub fn powf(&self, p: f64) -> Self {
+        if p.fract() == 0. && p >= 0. {
+            return self.pow(p as u32);
+        }
+
+        BigComplex::from_complex(self.to_complex().powf(p))
+    }
+
     /// returns the real part of the number as an f64
     pub fn real_f64(&self) -> f64 {
         self.real.to_f64().value()
@@ -517,6 +678,26 @@ impl ComplexNumber for BigComplex {
         }
     }
 
+    fn abs_real(&self) -> Self {
+        BigComplex {
+            real: match self.real < FBig::<mode::Zero>::ZERO {
+                true => self.real.clone().neg(),
+                false => self.real.clone()
+            },
+            im: self.im.clone()
+        }
+    }
+
+    fn abs_im(&self) -> Self {
+        BigComplex {
+            real: self.real.clone(),
+            im: match self.im < FBig::<mode::Zero>::ZERO {
+                true => self.im.clone().neg(),
+                false => self.im.clone()
+            }
+        }
+    }
+
     fn arg(&self) -> f64 {
         f64::atan2(self.im.to_f64().value(), self.real.to_f64().value())
     }
@@ -524,18 +705,19 @@ impl ComplexNumber for BigComplex {
     fn distance2_to(&self, other: ComplexType) -> f64 {
         match other {
             ComplexType::Double(c) => (self.clone()-BigComplex::from_complex(c)).abs_squared(),
+            ComplexType::DoubleDouble(c) => (self.clone()-c.to_big()).abs_squared(),
             ComplexType::Big(c) => (self.clone()-c).abs_squared()
         }
     }
 
     fn update_real_from_string(&mut self, new: String) {
-        if let Ok(new) = FBig::<mode::Zero, 10>::from_str_native(&new) {
-            self.real = new.with_base::<2>().value();
+        if let Some(new) = parse_decimal(&new) {
+            self.real = new;
         }
     }
     fn update_im_from_string(&mut self, new: String) {
-        if let Ok(new) = FBig::<mode::Zero, 10>::from_str_native(&new) {
-            self.im = new.with_base::<2>().value();
+        if let Some(new) = parse_decimal(&new) {
+            self.im = new;
         }
     }
 }
@@ -764,6 +946,33 @@ mod tests {
         assert_eq!(Complex::new(-142., 65.), a3);
     }
 
+    #[test]
+    fn real_power() {
+        let a = Complex::new(0., 4.);
+        let root = a.powf(0.5);
+
+        assert!((root.real - 2f64.sqrt()).abs() < 1e-12);
+        assert!((root.im - 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn exp_ln() {
+        let a = Complex::new(0.3, -1.2);
+        let b = a.ln().exp();
+        let i_pi = Complex::new(0., PI).exp();
+
+        assert!((a - b).abs_squared() < 1e-24);
+        assert!((i_pi - Complex::new(-1., 0.)).abs_squared() < 1e-24);
+    }
+
+    #[test]
+    fn power_bigcomplex() {
+        let a = BigComplex::from_f64s(2., -5.);
+        let a3 = BigComplex::from_f64s(-142., 65.);
+
+        assert_eq!(a.pow(3), a3);
+    }
+
     #[test]
     fn complex_times() {
         let a = Complex::new(3., 5.);
@@ -0,0 +1,342 @@
+// © 2023 costott. All rights reserved. 
+// This code is provided for viewing purposes only. Copying, reproduction, 
+// or distribution of this code, in whole or in part, in any form or by any 
+// means, is strictly prohibited without prior written permission from the 
+// copyright owner.
+
+use std::ops::{Add, Mul, Div, Sub, Neg};
+use dashu_float::{FBig, round::mode};
+
+use super::complex::*;
+
+/// significant figures double-doubles are written with
+/// (a little under their ~106 bits, so written values read back the same)
+const DOUBLE_DOUBLE_DIGITS: usize = 32;
+
+/// a + b, with the rounding error of the sum
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_part = sum - a;
+    (sum, (a - (sum - b_part)) + (b - b_part))
+}
+
+/// a + b, with the rounding error of the sum,
+/// only when |a| >= |b|
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    (sum, b - (sum - a))
+}
+
+/// splits a double into two halves with non-overlapping 26 bit mantissas,
+/// so their products are exact
+fn split(a: f64) -> (f64, f64) {
+    let t = 134217729. * a; // 2^27 + 1
+    let hi = t - (t - a);
+    (hi, a - hi)
+}
+
+/// a * b, with the rounding error of the product
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    (product, ((a_hi*b_hi - product) + a_hi*b_lo + a_lo*b_hi) + a_lo*b_lo)
+}
+
+/// writes a base 10 number without the trailing zeros of its fractional part
+fn trim_zeros(number: String) -> String {
+    if !number.contains('.') {
+        return number;
+    }
+    number.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// the unevaluated sum of two doubles, the smaller holding the rounding
+/// error of the bigger, giving around 106 bits of precision
+pub struct DoubleDouble {
+    hi: f64,
+    lo: f64
+}
+impl DoubleDouble {
+    pub const ZERO: DoubleDouble = DoubleDouble { hi: 0., lo: 0. };
+
+    fn new(hi: f64, lo: f64) -> DoubleDouble {
+        let (hi, lo) = quick_two_sum(hi, lo);
+        DoubleDouble { hi, lo }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.hi + self.lo
+    }
+
+    pub fn from_fbig(x: &FBig) -> DoubleDouble {
+        let hi = x.to_f64().value();
+        let lo = (x - FBig::try_from(hi).unwrap()).to_f64().value();
+        DoubleDouble::new(hi, lo)
+    }
+
+    pub fn to_fbig(&self) -> FBig {
+        FBig::try_from(self.hi).unwrap().with_precision(128).value() + FBig::try_from(self.lo).unwrap()
+    }
+
+    /// parses a base 10 number, returning None if it's invalid
+    pub fn parse(number: &str) -> Option<DoubleDouble> {
+        parse_decimal(number).map(|x| DoubleDouble::from_fbig(&x))
+    }
+
+    pub fn square(&self) -> DoubleDouble {
+        *self * *self
+    }
+
+    pub fn abs(&self) -> DoubleDouble {
+        match self.hi < 0. {
+            true => -*self,
+            false => *self
+        }
+    }
+}
+impl From<f64> for DoubleDouble {
+    fn from(x: f64) -> DoubleDouble {
+        DoubleDouble { hi: x, lo: 0. }
+    }
+}
+impl std::fmt::Display for DoubleDouble {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let decimal = self.to_fbig().with_rounding::<mode::HalfEven>()
+            .with_base_and_precision::<10>(DOUBLE_DOUBLE_DIGITS).value();
+        write!(f, "{}", trim_zeros(decimal.to_string()))
+    }
+}
+impl Add for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn add(self, rhs: DoubleDouble) -> DoubleDouble {
+        let (hi, hi_error) = two_sum(self.hi, rhs.hi);
+        let (lo, lo_error) = two_sum(self.lo, rhs.lo);
+        let (hi, lo) = quick_two_sum(hi, hi_error + lo);
+        DoubleDouble::new(hi, lo + lo_error)
+    }
+}
+impl Sub for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn sub(self, rhs: DoubleDouble) -> DoubleDouble {
+        self + -rhs
+    }
+}
+impl Neg for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn neg(self) -> DoubleDouble {
+        DoubleDouble { hi: -self.hi, lo: -self.lo }
+    }
+}
+impl Mul for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn mul(self, rhs: DoubleDouble) -> DoubleDouble {
+        let (hi, error) = two_prod(self.hi, rhs.hi);
+        DoubleDouble::new(hi, error + self.hi*rhs.lo + self.lo*rhs.hi)
+    }
+}
+impl Mul<f64> for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn mul(self, rhs: f64) -> DoubleDouble {
+        let (hi, error) = two_prod(self.hi, rhs);
+        DoubleDouble::new(hi, error + self.lo*rhs)
+    }
+}
+impl Div for DoubleDouble {
+    type Output = DoubleDouble;
+
+    /// long division, one double of the quotient at a time
+    fn div(self, rhs: DoubleDouble) -> DoubleDouble {
+        let q1 = self.hi / rhs.hi;
+        let remainder = self - rhs * q1;
+        let q2 = remainder.hi / rhs.hi;
+        let remainder = remainder - rhs * q2;
+        let q3 = remainder.hi / rhs.hi;
+
+        DoubleDouble::new(q1, q2) + DoubleDouble::from(q3)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// a complex number made of double-doubles, for zooms just past
+/// the precision of a double, where arbitrary precision is very slow
+pub struct ComplexDD {
+    pub real: DoubleDouble,
+    pub im: DoubleDouble
+}
+impl ComplexDD {
+    pub fn new(real: DoubleDouble, im: DoubleDouble) -> ComplexDD {
+        ComplexDD { real, im }
+    }
+
+    pub fn from_complex(c: Complex) -> ComplexDD {
+        ComplexDD { real: DoubleDouble::from(c.real), im: DoubleDouble::from(c.im) }
+    }
+
+    pub fn from_big(c: &BigComplex) -> ComplexDD {
+        ComplexDD { real: DoubleDouble::from_fbig(&c.real), im: DoubleDouble::from_fbig(&c.im) }
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        Complex::new(self.real.to_f64(), self.im.to_f64())
+    }
+
+    pub fn to_big(&self) -> BigComplex {
+        BigComplex::new(self.real.to_fbig(), self.im.to_fbig())
+    }
+
+    /// raise the complex number to a given integer power
+    /// (by repeated squaring)
+    pub fn pow(&self, n: u32) -> Self {
+        let mut result = ComplexDD::from_complex(Complex::new(1., 0.));
+        let mut base = *self;
+        let mut n = n;
+        while n > 0 {
+            if n % 2 == 1 {
+                result = result * base;
+            }
+            base = base.square();
+            n /= 2;
+        }
+
+        result
+    }
+
+    /// raise the complex number to a given real power
+    ///
+    /// non-integer powers have no double-double trig, so are
+    /// calculated in double precision
+    pub fn powf(&self, p: f64) -> Self {
+        if p.fract() == 0. && p >= 0. {
+            return self.pow(p as u32);
+        }
+
+        ComplexDD::from_complex(self.to_complex().powf(p))
+    }
+}
+impl ComplexNumber for ComplexDD {
+    fn square(&self) -> Self {
+        ComplexDD {
+            real: self.real.square() - self.im.square(),
+            im: self.real * self.im * 2.
+        }
+    }
+
+    fn abs_squared(&self) -> f64 {
+        (self.real.square() + self.im.square()).to_f64()
+    }
+
+    fn conjugate(&self) -> Self {
+        ComplexDD { real: self.real, im: -self.im }
+    }
+
+    fn abs_real(&self) -> Self {
+        ComplexDD { real: self.real.abs(), im: self.im }
+    }
+
+    fn abs_im(&self) -> Self {
+        ComplexDD { real: self.real, im: self.im.abs() }
+    }
+
+    fn arg(&self) -> f64 {
+        f64::atan2(self.im.to_f64(), self.real.to_f64())
+    }
+
+    fn distance2_to(&self, other: ComplexType) -> f64 {
+        match other {
+            ComplexType::Double(c) => (*self - ComplexDD::from_complex(c)).abs_squared(),
+            ComplexType::DoubleDouble(c) => (*self - c).abs_squared(),
+            ComplexType::Big(c) => (*self - ComplexDD::from_big(&c)).abs_squared()
+        }
+    }
+
+    fn update_real_from_string(&mut self, new: String) {
+        if let Some(new) = DoubleDouble::parse(&new) {
+            self.real = new;
+        }
+    }
+    fn update_im_from_string(&mut self, new: String) {
+        if let Some(new) = DoubleDouble::parse(&new) {
+            self.im = new;
+        }
+    }
+}
+impl Add for ComplexDD {
+    type Output = ComplexDD;
+
+    fn add(self, rhs: ComplexDD) -> ComplexDD {
+        ComplexDD { real: self.real + rhs.real, im: self.im + rhs.im }
+    }
+}
+impl Sub for ComplexDD {
+    type Output = ComplexDD;
+
+    fn sub(self, rhs: ComplexDD) -> ComplexDD {
+        ComplexDD { real: self.real - rhs.real, im: self.im - rhs.im }
+    }
+}
+impl Mul for ComplexDD {
+    type Output = ComplexDD;
+
+    fn mul(self, rhs: ComplexDD) -> ComplexDD {
+        ComplexDD {
+            real: self.real * rhs.real - self.im * rhs.im,
+            im: self.real * rhs.im + self.im * rhs.real
+        }
+    }
+}
+impl Mul<f64> for ComplexDD {
+    type Output = ComplexDD;
+
+    fn mul(self, rhs: f64) -> ComplexDD {
+        ComplexDD { real: self.real * rhs, im: self.im * rhs }
+    }
+}
+impl Div for ComplexDD {
+    type Output = ComplexDD;
+
+    fn div(self, rhs: ComplexDD) -> ComplexDD {
+        let n = self * rhs.conjugate();
+        let d = rhs.real.square() + rhs.im.square();
+        ComplexDD { real: n.real / d, im: n.im / d }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn more_precise_than_double() {
+        let third = DoubleDouble::from(1.) / DoubleDouble::from(3.);
+        let tiny = DoubleDouble::from(1e-25);
+
+        assert!((((third + tiny) - third).to_f64() - 1e-25).abs() < 1e-32);
+        assert!((third * 3. - DoubleDouble::from(1.)).to_f64().abs() < 1e-31);
+    }
+
+    #[test]
+    fn string_roundtrip() {
+        let number = "-1.7490000000000000000000000001234";
+        let parsed = DoubleDouble::parse(number).unwrap();
+
+        assert_eq!(parsed.to_string(), number);
+        assert_eq!(DoubleDouble::parse("0.1").unwrap().to_string(), "0.1");
+        assert!(DoubleDouble::parse("one").is_none());
+    }
+
+    #[test]
+    fn big_matches_double_double() {
+        let z = BigComplex::from_string_base10("-1.74900000000000000000001", "0.00000000000000000000003");
+        let z = z.with_precision(200);
+        let squared = ComplexDD::from_big(&z).square().to_big();
+
+        assert!((&squared - &z.square()).abs_squared() < 1e-60);
+    }
+}
@@ -0,0 +1,354 @@
+// © 2023 costott. All rights reserved. 
+// This code is provided for viewing purposes only. Copying, reproduction, 
+// or distribution of this code, in whole or in part, in any form or by any 
+// means, is strictly prohibited without prior written permission from the 
+// copyright owner.
+
+use std::cmp::Ordering;
+use std::f64::consts::{LN_2, LN_10};
+use std::fmt;
+use std::ops::{Add, Mul, Div, Sub, Neg, MulAssign};
+use dashu_float::FBig;
+
+use super::complex::*;
+
+/// the smallest exponent a value can have and still be used as a double
+/// (leaving room before doubles start losing precision)
+const DOUBLE_MIN_EXPONENT: i64 = -960;
+/// the largest exponent a value can have and still be used as a double
+const DOUBLE_MAX_EXPONENT: i64 = 1000;
+
+/// splits a double into a mantissa in [0.5, 1) and a power of 2
+fn frexp(x: f64) -> (f64, i64) {
+    if x == 0. || !x.is_finite() {
+        return (x, 0);
+    }
+
+    let bits = x.to_bits();
+    let exponent_bits = ((bits >> 52) & 0x7ff) as i64;
+    // subnormal, so scale it up to get a full mantissa
+    if exponent_bits == 0 {
+        let (mantissa, exponent) = frexp(x * 2f64.powi(54));
+        return (mantissa, exponent - 54);
+    }
+
+    let mantissa = f64::from_bits((bits & !(0x7ff << 52)) | (1022 << 52));
+    (mantissa, exponent_bits - 1022)
+}
+
+/// multiplies a double by 2^exponent, without overflowing part way
+fn ldexp(x: f64, exponent: i64) -> f64 {
+    let exponent = exponent.clamp(-2200, 2200) as i32;
+    x * 2f64.powi(exponent / 2) * 2f64.powi(exponent - exponent / 2)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// a double with a separate exponent, so it can be
+/// far bigger or smaller than a double can hold
+///
+/// the value is mantissa * 2^exponent
+pub struct FloatExp {
+    /// between 0.5 and 1 (or 0)
+    mantissa: f64,
+    exponent: i64
+}
+impl FloatExp {
+    pub const ZERO: FloatExp = FloatExp { mantissa: 0., exponent: 0 };
+
+    pub fn new(mantissa: f64, exponent: i64) -> FloatExp {
+        let (mantissa, extra) = frexp(mantissa);
+        if mantissa == 0. {
+            return FloatExp::ZERO;
+        }
+        FloatExp { mantissa, exponent: exponent + extra }
+    }
+
+    /// the closest double to the value
+    /// (0 or infinity when out of range)
+    pub fn to_f64(&self) -> f64 {
+        ldexp(self.mantissa, self.exponent)
+    }
+
+    pub fn to_fbig(&self) -> FBig {
+        FBig::try_from(self.mantissa).unwrap() << self.exponent as isize
+    }
+
+    /// returns if the value can be used as a double without losing precision
+    pub fn fits_double(&self) -> bool {
+        self.mantissa == 0. || (DOUBLE_MIN_EXPONENT..=DOUBLE_MAX_EXPONENT).contains(&self.exponent)
+    }
+
+    pub fn abs(&self) -> FloatExp {
+        FloatExp { mantissa: self.mantissa.abs(), exponent: self.exponent }
+    }
+
+    pub fn sqrt(&self) -> FloatExp {
+        match self.exponent % 2 == 0 {
+            true => FloatExp::new(self.mantissa.sqrt(), self.exponent / 2),
+            false => FloatExp::new((self.mantissa * 2.).sqrt(), (self.exponent - 1) / 2)
+        }
+    }
+
+    pub fn ln(&self) -> f64 {
+        self.mantissa.ln() + self.exponent as f64 * LN_2
+    }
+
+    /// e^x, for when the result won't fit in a double
+    pub fn exp(x: f64) -> FloatExp {
+        let exponent = (x / LN_2).floor();
+        FloatExp::new((x - exponent * LN_2).exp(), exponent as i64)
+    }
+
+    /// raise to a given integer power
+    /// (by repeated squaring)
+    pub fn powi(&self, n: i64) -> FloatExp {
+        let mut result = FloatExp::from(1.);
+        let mut base = *self;
+        let mut m = n.unsigned_abs();
+        while m > 0 {
+            if m % 2 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            m /= 2;
+        }
+
+        match n < 0 {
+            true => FloatExp::from(1.) / result,
+            false => result
+        }
+    }
+
+    /// parses a decimal number, which can have an exponent
+    /// past the range of a double (e.g. 1.5e-2000)
+    pub fn parse(number: &str) -> Result<FloatExp, &'static str> {
+        let number = number.trim();
+        let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent),
+            None => (number, "0")
+        };
+
+        let mantissa = mantissa.parse::<f64>().map_err(|_| "invalid number")?;
+        let exponent = exponent.parse::<i64>().map_err(|_| "invalid exponent")?;
+        if !mantissa.is_finite() {
+            return Err("invalid number");
+        }
+
+        Ok(FloatExp::from(mantissa) * FloatExp::from(10.).powi(exponent))
+    }
+}
+impl From<f64> for FloatExp {
+    fn from(x: f64) -> FloatExp {
+        FloatExp::new(x, 0)
+    }
+}
+impl fmt::Display for FloatExp {
+    /// written like a double when in range,
+    /// otherwise as a mantissa and base 10 exponent
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let abs = self.abs();
+        if self.mantissa == 0. || (abs > FloatExp::from(1e-300) && abs < FloatExp::from(1e300)) {
+            return write!(f, "{}", self.to_f64());
+        }
+
+        let mut exponent = (abs.ln() / LN_10).floor() as i64;
+        let mut mantissa = (*self / FloatExp::from(10.).powi(exponent)).to_f64();
+        // the log can be slightly off around powers of 10
+        if mantissa.abs() >= 10. {
+            mantissa /= 10.;
+            exponent += 1;
+        } else if mantissa.abs() < 1. {
+            mantissa *= 10.;
+            exponent -= 1;
+        }
+
+        write!(f, "{}e{}", mantissa, exponent)
+    }
+}
+impl PartialOrd for FloatExp {
+    fn partial_cmp(&self, other: &FloatExp) -> Option<Ordering> {
+        (*self - *other).mantissa.partial_cmp(&0.)
+    }
+}
+impl Add for FloatExp {
+    type Output = FloatExp;
+
+    fn add(self, rhs: FloatExp) -> FloatExp {
+        if self.mantissa == 0. { return rhs }
+        if rhs.mantissa == 0. { return self }
+
+        let (big, small) = match self.exponent >= rhs.exponent {
+            true => (self, rhs),
+            false => (rhs, self)
+        };
+        // too small to change the bigger value
+        if big.exponent - small.exponent > 64 {
+            return big;
+        }
+
+        FloatExp::new(big.mantissa + ldexp(small.mantissa, small.exponent - big.exponent), big.exponent)
+    }
+}
+impl Sub for FloatExp {
+    type Output = FloatExp;
+
+    fn sub(self, rhs: FloatExp) -> FloatExp {
+        self + -rhs
+    }
+}
+impl Neg for FloatExp {
+    type Output = FloatExp;
+
+    fn neg(self) -> FloatExp {
+        FloatExp { mantissa: -self.mantissa, exponent: self.exponent }
+    }
+}
+impl Mul for FloatExp {
+    type Output = FloatExp;
+
+    fn mul(self, rhs: FloatExp) -> FloatExp {
+        FloatExp::new(self.mantissa * rhs.mantissa, self.exponent + rhs.exponent)
+    }
+}
+impl Mul<f64> for FloatExp {
+    type Output = FloatExp;
+
+    fn mul(self, rhs: f64) -> FloatExp {
+        self * FloatExp::from(rhs)
+    }
+}
+impl Mul<FloatExp> for f64 {
+    type Output = FloatExp;
+
+    fn mul(self, rhs: FloatExp) -> FloatExp {
+        FloatExp::from(self) * rhs
+    }
+}
+impl MulAssign<f64> for FloatExp {
+    fn mul_assign(&mut self, rhs: f64) {
+        *self = *self * rhs;
+    }
+}
+impl Div for FloatExp {
+    type Output = FloatExp;
+
+    fn div(self, rhs: FloatExp) -> FloatExp {
+        FloatExp::new(self.mantissa / rhs.mantissa, self.exponent - rhs.exponent)
+    }
+}
+impl Div<f64> for FloatExp {
+    type Output = FloatExp;
+
+    fn div(self, rhs: f64) -> FloatExp {
+        self / FloatExp::from(rhs)
+    }
+}
+impl Div<FloatExp> for f64 {
+    type Output = FloatExp;
+
+    fn div(self, rhs: FloatExp) -> FloatExp {
+        FloatExp::from(self) / rhs
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// a complex number made of extended exponent floats,
+/// used for perturbation deltas too small for a double
+pub struct ComplexExp {
+    pub real: FloatExp,
+    pub im: FloatExp
+}
+impl ComplexExp {
+    pub const ZERO: ComplexExp = ComplexExp { real: FloatExp::ZERO, im: FloatExp::ZERO };
+
+    pub fn new(real: FloatExp, im: FloatExp) -> ComplexExp {
+        ComplexExp { real, im }
+    }
+
+    pub fn from_complex(c: Complex) -> ComplexExp {
+        ComplexExp { real: FloatExp::from(c.real), im: FloatExp::from(c.im) }
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        Complex::new(self.real.to_f64(), self.im.to_f64())
+    }
+
+    /// returns if both parts can be used as doubles without losing precision
+    pub fn fits_double(&self) -> bool {
+        self.real.fits_double() && self.im.fits_double()
+    }
+
+    pub fn square(&self) -> ComplexExp {
+        ComplexExp {
+            real: self.real * self.real - self.im * self.im,
+            im: self.real * self.im * 2.
+        }
+    }
+
+    /// the absolute value of the complex number, squared
+    pub fn abs_squared(&self) -> FloatExp {
+        self.real * self.real + self.im * self.im
+    }
+}
+impl Add for ComplexExp {
+    type Output = ComplexExp;
+
+    fn add(self, rhs: ComplexExp) -> ComplexExp {
+        ComplexExp { real: self.real + rhs.real, im: self.im + rhs.im }
+    }
+}
+impl Sub for ComplexExp {
+    type Output = ComplexExp;
+
+    fn sub(self, rhs: ComplexExp) -> ComplexExp {
+        ComplexExp { real: self.real - rhs.real, im: self.im - rhs.im }
+    }
+}
+impl Mul for ComplexExp {
+    type Output = ComplexExp;
+
+    fn mul(self, rhs: ComplexExp) -> ComplexExp {
+        ComplexExp {
+            real: self.real * rhs.real - self.im * rhs.im,
+            im: self.real * rhs.im + self.im * rhs.real
+        }
+    }
+}
+impl Mul<FloatExp> for ComplexExp {
+    type Output = ComplexExp;
+
+    fn mul(self, rhs: FloatExp) -> ComplexExp {
+        ComplexExp { real: self.real * rhs, im: self.im * rhs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beyond_double_range() {
+        let tiny = FloatExp::parse("1.5e-2000").unwrap();
+        let squared = tiny * tiny;
+
+        assert!(!tiny.fits_double());
+        assert!(squared < tiny && squared > FloatExp::ZERO);
+        assert!(((squared / tiny) / tiny - FloatExp::from(1.)).abs() < FloatExp::from(1e-12));
+        assert!(((tiny + tiny) / tiny).to_f64() == 2.);
+        assert!((tiny.sqrt().ln() - tiny.ln() / 2.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_display_roundtrip() {
+        for number in ["0", "0.005", "250000", "-3.25", "1.5e-2000", "7e4000"] {
+            let parsed = FloatExp::parse(number).unwrap();
+            let reparsed = FloatExp::parse(&parsed.to_string()).unwrap();
+            assert!(((parsed - reparsed) / parsed).abs() < FloatExp::from(1e-12) || parsed == FloatExp::ZERO);
+        }
+
+        assert_eq!(FloatExp::parse("0.005").unwrap().to_string(), "0.005");
+        assert!(FloatExp::parse("1.5e-2000").unwrap().to_string().ends_with("e-2000"));
+        assert!(FloatExp::parse("1e").is_err());
+        assert!(FloatExp::parse("big").is_err());
+    }
+}
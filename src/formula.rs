@@ -0,0 +1,466 @@
+// © 2023 costott. All rights reserved. 
+// This code is provided for viewing purposes only. Copying, reproduction, 
+// or distribution of this code, in whole or in part, in any form or by any 
+// means, is strictly prohibited without prior written permission from the 
+// copyright owner.
+
+use super::complex::*;
+use super::doubledouble::ComplexDD;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Function {
+    Sin,
+    Cos,
+    Sinh,
+    Cosh,
+    Exp,
+    Ln,
+    Sqrt,
+    Conj,
+    /// |re| + i|im|
+    Abs
+}
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        Some(match name {
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            "sinh" => Function::Sinh,
+            "cosh" => Function::Cosh,
+            "exp" => Function::Exp,
+            "ln" | "log" => Function::Ln,
+            "sqrt" => Function::Sqrt,
+            "conj" => Function::Conj,
+            "abs" => Function::Abs,
+            _ => return None
+        })
+    }
+
+    fn apply_double(&self, z: Complex) -> Complex {
+        match self {
+            Function::Sin => z.sin(),
+            Function::Cos => z.cos(),
+            Function::Sinh => z.sinh(),
+            Function::Cosh => z.cosh(),
+            Function::Exp => z.exp(),
+            Function::Ln => z.ln(),
+            Function::Sqrt => z.powf(0.5),
+            Function::Conj => z.conjugate(),
+            Function::Abs => z.abs_real().abs_im()
+        }
+    }
+
+    /// returns if the function has an arbitrary precision version
+    fn supports_big(&self) -> bool {
+        matches!(self, Function::Conj | Function::Abs)
+    }
+
+    /// # Panics
+    /// the function doesn't have a double-double version
+    /// (the same functions as arbitrary precision)
+    fn apply_double_double(&self, z: ComplexDD) -> ComplexDD {
+        match self {
+            Function::Conj => z.conjugate(),
+            Function::Abs => z.abs_real().abs_im(),
+            _ => panic!("no double-double version of function")
+        }
+    }
+
+    /// # Panics
+    /// the function doesn't have an arbitrary precision version
+    fn apply_big(&self, z: &BigComplex) -> BigComplex {
+        match self {
+            Function::Conj => z.conjugate(),
+            Function::Abs => z.abs_real().abs_im(),
+            _ => panic!("no arbitrary precision version of function")
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Expression {
+    Z,
+    C,
+    /// a number stored in double and arbitrary precision
+    Constant(Complex, BigComplex),
+    Add(Box<Expression>, Box<Expression>),
+    Sub(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+    Div(Box<Expression>, Box<Expression>),
+    Neg(Box<Expression>),
+    /// raised to a constant real power
+    PowReal(Box<Expression>, f64),
+    /// raised to any other power
+    Pow(Box<Expression>, Box<Expression>),
+    Function(Function, Box<Expression>)
+}
+impl Expression {
+    fn constant(value: Complex) -> Expression {
+        Expression::Constant(value, BigComplex::from_complex(value))
+    }
+
+    /// returns if the expression doesn't depend on z or c
+    fn is_constant(&self) -> bool {
+        match self {
+            Expression::Z | Expression::C => false,
+            Expression::Constant(..) => true,
+            Expression::Add(a, b) | Expression::Sub(a, b) | Expression::Mul(a, b) |
+            Expression::Div(a, b) | Expression::Pow(a, b) => a.is_constant() && b.is_constant(),
+            Expression::Neg(a) | Expression::PowReal(a, _) | Expression::Function(_, a) => a.is_constant()
+        }
+    }
+
+    /// returns if every operation in the expression has an arbitrary precision version
+    fn supports_big(&self) -> bool {
+        match self {
+            Expression::Z | Expression::C | Expression::Constant(..) => true,
+            Expression::Add(a, b) | Expression::Sub(a, b) |
+            Expression::Mul(a, b) | Expression::Div(a, b) => a.supports_big() && b.supports_big(),
+            Expression::Neg(a) => a.supports_big(),
+            // fractional and negative powers are only calculated in doubles
+            Expression::PowReal(a, p) => p.fract() == 0. && *p >= 0. && a.supports_big(),
+            Expression::Pow(..) => false,
+            Expression::Function(f, a) => f.supports_big() && a.supports_big()
+        }
+    }
+
+    fn evaluate_double(&self, z: Complex, c: Complex) -> Complex {
+        match self {
+            Expression::Z => z,
+            Expression::C => c,
+            Expression::Constant(value, _) => *value,
+            Expression::Add(a, b) => a.evaluate_double(z, c) + b.evaluate_double(z, c),
+            Expression::Sub(a, b) => a.evaluate_double(z, c) - b.evaluate_double(z, c),
+            Expression::Mul(a, b) => a.evaluate_double(z, c) * b.evaluate_double(z, c),
+            Expression::Div(a, b) => a.evaluate_double(z, c) / b.evaluate_double(z, c),
+            Expression::Neg(a) => a.evaluate_double(z, c) * -1.,
+            Expression::PowReal(a, p) => match *p == 2. {
+                true => a.evaluate_double(z, c).square(),
+                false => a.evaluate_double(z, c).powf(*p)
+            },
+            Expression::Pow(a, b) => a.evaluate_double(z, c).powc(b.evaluate_double(z, c)),
+            Expression::Function(f, a) => f.apply_double(a.evaluate_double(z, c))
+        }
+    }
+
+    /// # Panics
+    /// the expression doesn't support arbitrary precision
+    /// (double-doubles support the same operations)
+    fn evaluate_double_double(&self, z: ComplexDD, c: ComplexDD) -> ComplexDD {
+        match self {
+            Expression::Z => z,
+            Expression::C => c,
+            Expression::Constant(value, _) => ComplexDD::from_complex(*value),
+            Expression::Add(a, b) => a.evaluate_double_double(z, c) + b.evaluate_double_double(z, c),
+            Expression::Sub(a, b) => a.evaluate_double_double(z, c) - b.evaluate_double_double(z, c),
+            Expression::Mul(a, b) => a.evaluate_double_double(z, c) * b.evaluate_double_double(z, c),
+            Expression::Div(a, b) => a.evaluate_double_double(z, c) / b.evaluate_double_double(z, c),
+            Expression::Neg(a) => a.evaluate_double_double(z, c) * -1.,
+            Expression::PowReal(a, p) => match *p == 2. {
+                true => a.evaluate_double_double(z, c).square(),
+                false => a.evaluate_double_double(z, c).powf(*p)
+            },
+            Expression::Pow(..) => panic!("no double-double complex powers"),
+            Expression::Function(f, a) => f.apply_double_double(a.evaluate_double_double(z, c))
+        }
+    }
+
+    /// # Panics
+    /// the expression doesn't support arbitrary precision
+    fn evaluate_big(&self, z: &BigComplex, c: &BigComplex) -> BigComplex {
+        match self {
+            Expression::Z => z.clone(),
+            Expression::C => c.clone(),
+            Expression::Constant(_, value) => value.clone(),
+            Expression::Add(a, b) => &a.evaluate_big(z, c) + &b.evaluate_big(z, c),
+            Expression::Sub(a, b) => &a.evaluate_big(z, c) - &b.evaluate_big(z, c),
+            Expression::Mul(a, b) => &a.evaluate_big(z, c) * &b.evaluate_big(z, c),
+            Expression::Div(a, b) => &a.evaluate_big(z, c) / &b.evaluate_big(z, c),
+            Expression::Neg(a) => a.evaluate_big(z, c) * -1.,
+            Expression::PowReal(a, p) => match *p == 2. {
+                true => a.evaluate_big(z, c).square(),
+                false => a.evaluate_big(z, c).powf(*p)
+            },
+            Expression::Pow(..) => panic!("no arbitrary precision complex powers"),
+            Expression::Function(f, a) => f.apply_big(&a.evaluate_big(z, c))
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(char)
+}
+
+/// splits the formula text into numbers, names and symbols
+fn tokenise(text: &str) -> Result<Vec<Token>, &'static str> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let ch = chars[i];
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch.is_ascii_digit() || ch == '.' {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            match number.parse::<f64>() {
+                Err(_) => return Err("invalid number"),
+                Ok(n) => tokens.push(Token::Number(n))
+            }
+        } else if ch.is_alphabetic() {
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            tokens.push(Token::Name(chars[start..i].iter().collect::<String>().to_lowercase()));
+        } else if "+-*/^()".contains(ch) {
+            tokens.push(Token::Symbol(ch));
+            i += 1;
+        } else {
+            return Err("unexpected character");
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// recursive descent parser turning tokens into an expression
+///
+/// expression = term (('+' | '-') term)*
+///
+/// term = unary (('*' | '/')? unary)*
+///
+/// unary = '-' unary | power
+///
+/// power = primary ('^' unary)?
+///
+/// primary = number | name | function '(' expression ')' | '(' expression ')'
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize
+}
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next_is(&self, symbol: char) -> bool {
+        self.peek() == Some(&Token::Symbol(symbol))
+    }
+
+    fn expression(&mut self) -> Result<Expression, &'static str> {
+        let mut expression = self.term()?;
+        loop {
+            if self.next_is('+') {
+                self.position += 1;
+                expression = Expression::Add(Box::new(expression), Box::new(self.term()?));
+            } else if self.next_is('-') {
+                self.position += 1;
+                expression = Expression::Sub(Box::new(expression), Box::new(self.term()?));
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, &'static str> {
+        let mut term = self.unary()?;
+        loop {
+            if self.next_is('*') {
+                self.position += 1;
+                term = Expression::Mul(Box::new(term), Box::new(self.unary()?));
+            } else if self.next_is('/') {
+                self.position += 1;
+                term = Expression::Div(Box::new(term), Box::new(self.unary()?));
+            } else if matches!(self.peek(), Some(Token::Number(_)) | Some(Token::Name(_))) || self.next_is('(') {
+                // implicit multiplication, like 2z
+                term = Expression::Mul(Box::new(term), Box::new(self.unary()?));
+            } else {
+                return Ok(term);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expression, &'static str> {
+        if self.next_is('-') {
+            self.position += 1;
+            return Ok(Expression::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Expression, &'static str> {
+        let base = self.primary()?;
+        if !self.next_is('^') {
+            return Ok(base);
+        }
+        self.position += 1;
+
+        let exponent = self.unary()?;
+        if !exponent.is_constant() {
+            return Ok(Expression::Pow(Box::new(base), Box::new(exponent)));
+        }
+        let exponent_value = exponent.evaluate_double(Complex::new(0., 0.), Complex::new(0., 0.));
+        Ok(match exponent_value.im == 0. {
+            true => Expression::PowReal(Box::new(base), exponent_value.real),
+            false => Expression::Pow(Box::new(base), Box::new(Expression::constant(exponent_value)))
+        })
+    }
+
+    fn primary(&mut self) -> Result<Expression, &'static str> {
+        let token = match self.peek() {
+            None => return Err("unexpected end of formula"),
+            Some(token) => token.clone()
+        };
+        self.position += 1;
+
+        match token {
+            Token::Number(n) => Ok(Expression::constant(Complex::new(n, 0.))),
+            Token::Symbol('(') => self.bracketed(),
+            Token::Symbol(_) => Err("unexpected symbol"),
+            Token::Name(name) => match name.as_str() {
+                "z" => Ok(Expression::Z),
+                "c" => Ok(Expression::C),
+                "i" => Ok(Expression::constant(Complex::new(0., 1.))),
+                name => match Function::from_name(name) {
+                    None => Err("unknown name"),
+                    Some(function) => {
+                        if !self.next_is('(') {
+                            return Err("functions need brackets");
+                        }
+                        self.position += 1;
+                        Ok(Expression::Function(function, Box::new(self.bracketed()?)))
+                    }
+                }
+            }
+        }
+    }
+
+    /// parses the expression inside brackets, after the opening bracket
+    fn bracketed(&mut self) -> Result<Expression, &'static str> {
+        let expression = self.expression()?;
+        if !self.next_is(')') {
+            return Err("missing closing bracket");
+        }
+        self.position += 1;
+        Ok(expression)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+/// a user entered formula for the next z, in terms of z and c
+pub struct Formula {
+    text: String,
+    expression: Expression,
+    /// whether every operation has an arbitrary precision version
+    supports_big: bool
+}
+impl Formula {
+    /// # Errors
+    /// the text isn't a valid formula
+    pub fn parse(text: &str) -> Result<Formula, &'static str> {
+        let tokens = tokenise(text)?;
+        if tokens.is_empty() {
+            return Err("formula can't be empty");
+        }
+
+        let mut parser = Parser { tokens, position: 0 };
+        let expression = parser.expression()?;
+        if parser.position != parser.tokens.len() {
+            return Err(match parser.next_is(')') {
+                true => "missing opening bracket",
+                false => "unexpected symbol"
+            });
+        }
+
+        Ok(Formula {
+            text: text.trim().to_string(),
+            supports_big: expression.supports_big(),
+            expression
+        })
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn evaluate_double(&self, z: Complex, c: Complex) -> Complex {
+        self.expression.evaluate_double(z, c)
+    }
+
+    /// evaluates the formula with double-doubles,
+    /// falling back to double precision if any operations don't support it
+    pub fn evaluate_double_double(&self, z: ComplexDD, c: ComplexDD) -> ComplexDD {
+        match self.supports_big {
+            true => self.expression.evaluate_double_double(z, c),
+            false => ComplexDD::from_complex(self.evaluate_double(z.to_complex(), c.to_complex()))
+        }
+    }
+
+    /// evaluates the formula in arbitrary precision,
+    /// falling back to double precision if any operations don't support it
+    pub fn evaluate_big(&self, z: &BigComplex, c: &BigComplex) -> BigComplex {
+        match self.supports_big {
+            true => self.expression.evaluate_big(z, c),
+            false => BigComplex::from_complex(self.evaluate_double(z.to_complex(), c.to_complex()))
+        }
+    }
+}
+impl Default for Formula {
+    /// z^2 + c
+    fn default() -> Formula {
+        Formula::parse("z^2 + c").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precedence() {
+        let formula = Formula::parse("-z^2 + 2c*3").unwrap();
+        let z = Complex::new(1., 2.);
+        let c = Complex::new(0.5, -1.);
+
+        assert_eq!(formula.evaluate_double(z, c), z.square() * -1. + c * 6.);
+    }
+
+    #[test]
+    fn functions() {
+        let formula = Formula::parse("z^3 + c*sin(z) + 0.2").unwrap();
+        let z = Complex::new(0.3, 0.1);
+        let c = Complex::new(-0.5, 0.2);
+
+        assert_eq!(formula.evaluate_double(z, c), z.powf(3.) + c * z.sin() + Complex::new(0.2, 0.));
+        assert!(!formula.supports_big);
+    }
+
+    #[test]
+    fn big_matches_double() {
+        let formula = Formula::parse("conj(z)^2 / (z - i) + c").unwrap();
+        let z = Complex::new(0.3, 0.1);
+        let c = Complex::new(-0.5, 0.2);
+        let big = formula.evaluate_big(&BigComplex::from_complex(z), &BigComplex::from_complex(c));
+
+        assert!(formula.supports_big);
+        assert!(!Formula::parse("z^1.5 + c").unwrap().supports_big);
+        assert!(!Formula::parse("z^-2 + c").unwrap().supports_big);
+        assert!((big.to_complex() - formula.evaluate_double(z, c)).abs_squared() < 1e-24);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Formula::parse("z^2 + ").unwrap_err(), "unexpected end of formula");
+        assert_eq!(Formula::parse("(z + c").unwrap_err(), "missing closing bracket");
+        assert_eq!(Formula::parse("z + c)").unwrap_err(), "missing opening bracket");
+        assert_eq!(Formula::parse("tan(z)").unwrap_err(), "unknown name");
+        assert_eq!(Formula::parse("z $ c").unwrap_err(), "unexpected character");
+    }
+}
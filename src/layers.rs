@@ -12,39 +12,195 @@ use crate::palettes::Palette;
 
 use super::{*, menu::DropDownType, get_str_between};
 
+/// squared distance z has to come back within to have fallen into a cycle
+/// (around the precision of a double, so escaping points near a cycle aren't caught)
+const PERIOD_EPSILON2: f64 = 1e-30;
+/// squared distance z has to come back within to have fallen into a cycle, 
+/// for double-doubles
+const PERIOD_EPSILON2_DOUBLE_DOUBLE: f64 = 1e-60;
+/// bits of precision z doesn't have to come back within to have fallen into a cycle,
+/// for arbitrary precision (as rounding errors make the last bits noise)
+const PERIOD_SLACK_BITS: usize = 6;
+
+#[derive(Clone)]
+/// everything deciding where an orbit goes next, 
+/// as formulas using the previous z or a hybrid's phase can pass through the same z without repeating
+struct OrbitState<T> {
+    z: T,
+    previous_z: T,
+    phase: u32
+}
+impl<T: Clone> OrbitState<T> {
+    fn new(fractal: &Fractal, z: &T, memory: &OrbitMemory<T>) -> OrbitState<T> {
+        OrbitState { z: z.clone(), previous_z: memory.previous_z.clone(), phase: fractal.orbit_phase(memory) }
+    }
+}
+
+#[derive(Clone)]
+/// brent's cycle detection, finding when an orbit falls into a cycle
+/// by comparing each state to a saved state, which moves up to it every power of 2 iterations
+/// 
+/// distances are compared in `D`, so they can be as small as the orbit's precision
+struct CycleDetector<T, D> {
+    /// none until the first state is seen
+    saved: Option<OrbitState<T>>,
+    /// iterations since the state was saved
+    steps: u32,
+    /// iterations between the state being saved
+    power: u32,
+    epsilon2: D,
+    /// length of the cycle, once it's found
+    period: Option<u32>
+}
+impl<T: Clone, D: PartialOrd> CycleDetector<T, D> {
+    /// `period` is given if it's already known
+    fn new(epsilon2: D, period: Option<u32>) -> CycleDetector<T, D> {
+        CycleDetector { saved: None, steps: 0, power: 1, epsilon2, period }
+    }
+
+    /// compares the next state to the saved state, given the squared distance between zs
+    fn check(&mut self, state: OrbitState<T>, distance2: impl Fn(&T, &T) -> D) {
+        if self.period.is_some() { return }
+        let Some(saved) = &self.saved else {
+            self.saved = Some(state);
+            return
+        };
+
+        self.steps += 1;
+        if state.phase == saved.phase && distance2(&state.z, &saved.z) < self.epsilon2 &&
+            distance2(&state.previous_z, &saved.previous_z) < self.epsilon2 {
+            self.period = Some(self.steps);
+        } else if self.steps == self.power {
+            self.saved = Some(state);
+            self.steps = 0;
+            self.power *= 2;
+        }
+    }
+}
+
+/// returns if iterating can stop once a point is known to be in the set,
+/// which is when none of the implementors need to see every z
+fn can_stop_early(implementations: &[LayerImplementation]) -> bool {
+    !implementations.iter().any(|im| im.needs_every_iteration())
+}
+
 /// analyse the given complex number, letting the implementors
 /// calculate their outputs
 /// 
 /// # For double precision complex numbers
 /// 
+/// points inside the mandelbrot set's main cardioid and bulb,
+/// or whose orbit falls into a cycle, are in the set without
+/// iterating to the max iterations (when no implementor needs every z)
+/// 
 /// # Returns
-/// returns if the point is in the set or not
-fn diverges_implementors_double(fractal: &Fractal, c: Complex, max_iterations: u32, bailout2: f64, implementations: &mut Vec<LayerImplementation>) -> bool {
-    let mut z = c;
+/// returns where the point ended up
+fn diverges_implementors_double(fractal: &Fractal, c: Complex, max_iterations: u32, bailout2: f64, implementations: &mut Vec<LayerImplementation>) -> SetRegion {
+    let mut z = fractal.start_z_double(c);
+    let mut memory = OrbitMemory::new_double();
     for im in implementations.iter_mut() {
-        im.before(max_iterations, bailout2);
+        im.before(fractal, max_iterations, bailout2);
     }
+    let stop_early = can_stop_early(implementations);
+    let mut cycle = CycleDetector::new(PERIOD_EPSILON2, fractal.known_interior_double(c));
 
     for i in 0..max_iterations {        
         // if z.real.abs() > BAILOUT_ORBIT_TRAP || z.im.abs() > BAILOUT_ORBIT_TRAP { <- much faster but less accurate
-        if z.abs_squared() > bailout2 {
-            for im in implementations.iter_mut() {
-                im.out_set_double(z, i);
+        match fractal.finished_double(z, &memory, bailout2) {
+            None => {},
+            Some(SetRegion::Converged) => {
+                for im in implementations.iter_mut() {
+                    im.converged_double(z, i);
+                }
+                return SetRegion::Converged;
+            },
+            Some(region) => {
+                for im in implementations.iter_mut() {
+                    im.out_set_double(z, i);
+                }
+                return region;
             }
-            return false;
         }
 
+        if stop_early && cycle.period.is_some() { break }
+
         for im in implementations.iter_mut() {
             im.during_double(z, i);
         }
 
-        fractal.iterate_double(&mut z, c)
+        fractal.iterate_double(&mut z, c, &mut memory);
+        if fractal.can_detect_period() {
+            cycle.check(OrbitState::new(fractal, &z, &memory), |z1, z2| (*z1 - *z2).abs_squared());
+        }
     }
 
     for im in implementations.iter_mut() {
+        if let Some(period) = cycle.period {
+            im.found_period(period);
+        }
         im.in_set_double(z);
     }
-    return true;
+    SetRegion::InSet
+}
+
+/// analyse the given complex number, letting the implementors
+/// calculate their outputs
+/// 
+/// # For double-double complex numbers
+/// 
+/// the implementors are given each z as a double, 
+/// as they only need the extra precision for iterating
+/// 
+/// points whose orbit falls into a cycle are in the set without
+/// iterating to the max iterations (when no implementor needs every z)
+/// 
+/// # Returns
+/// returns where the point ended up
+fn diverges_implementors_double_double(fractal: &Fractal, c: ComplexDD, max_iterations: u32, bailout2: f64, implementations: &mut Vec<LayerImplementation>) -> SetRegion {
+    let mut z = fractal.start_z_double_double(c);
+    let mut memory = OrbitMemory::new_double_double();
+    for im in implementations.iter_mut() {
+        im.before(fractal, max_iterations, bailout2);
+    }
+    let stop_early = can_stop_early(implementations);
+    let mut cycle = CycleDetector::new(PERIOD_EPSILON2_DOUBLE_DOUBLE, fractal.known_interior_double(c.to_complex()));
+
+    for i in 0..max_iterations {
+        match fractal.finished_double_double(z, &memory, bailout2) {
+            None => {},
+            Some(SetRegion::Converged) => {
+                for im in implementations.iter_mut() {
+                    im.converged_double(z.to_complex(), i);
+                }
+                return SetRegion::Converged;
+            },
+            Some(region) => {
+                for im in implementations.iter_mut() {
+                    im.out_set_double(z.to_complex(), i);
+                }
+                return region;
+            }
+        }
+
+        if stop_early && cycle.period.is_some() { break }
+
+        for im in implementations.iter_mut() {
+            im.during_double(z.to_complex(), i);
+        }
+
+        fractal.iterate_double_double(&mut z, c, &mut memory);
+        if fractal.can_detect_period() {
+            cycle.check(OrbitState::new(fractal, &z, &memory), |z1, z2| (*z1 - *z2).abs_squared());
+        }
+    }
+
+    for im in implementations.iter_mut() {
+        if let Some(period) = cycle.period {
+            im.found_period(period);
+        }
+        im.in_set_double(z.to_complex());
+    }
+    SetRegion::InSet
 }
 
 /// analyse the given complex number, letting the implementors
@@ -52,85 +208,237 @@ fn diverges_implementors_double(fractal: &Fractal, c: Complex, max_iterations: u
 /// 
 /// # For arbitrary precision complex numbers
 /// 
+/// points inside the mandelbrot set's main cardioid and bulb,
+/// or whose orbit falls into a cycle, are in the set without
+/// iterating to the max iterations (when no implementor needs every z)
+/// 
+/// cycles are found to within c's precision (less a few bits of rounding errors)
+/// 
 /// # Returns
-/// returns if the point is in the set or not
-fn diverges_implementors_big(fractal: &Fractal, c: BigComplex, max_iterations: u32, bailout2: f64, implementations: &mut Vec<LayerImplementation>) -> bool {
-    let mut z = c.clone();
+/// returns where the point ended up
+fn diverges_implementors_big(fractal: &Fractal, c: BigComplex, max_iterations: u32, bailout2: f64, implementations: &mut Vec<LayerImplementation>) -> SetRegion {
+    let mut z = fractal.start_z_big(&c);
+    let mut memory = OrbitMemory::new_big();
     for im in implementations.iter_mut() {
-        im.before(max_iterations, bailout2);
+        im.before(fractal, max_iterations, bailout2);
     }
+    let stop_early = can_stop_early(implementations);
+    let epsilon2 = FBig::ONE >> (2 * c.precision().saturating_sub(PERIOD_SLACK_BITS)) as isize;
+    let mut cycle = CycleDetector::new(epsilon2, fractal.known_interior_big(&c));
 
     for i in 0..max_iterations {        
         // if z.real.abs() > BAILOUT_ORBIT_TRAP || z.im.abs() > BAILOUT_ORBIT_TRAP { <- much faster but less accurate
-        if z.abs_squared() > bailout2 {
-            for im in implementations.iter_mut() {
-                im.out_set_big(&z, i);
+        match fractal.finished_big(&z, &memory, bailout2) {
+            None => {},
+            Some(SetRegion::Converged) => {
+                for im in implementations.iter_mut() {
+                    im.converged_big(&z, i);
+                }
+                return SetRegion::Converged;
+            },
+            Some(region) => {
+                for im in implementations.iter_mut() {
+                    im.out_set_big(&z, i);
+                }
+                return region;
             }
-            return false;
         }
 
+        if stop_early && cycle.period.is_some() { break }
+
         for im in implementations.iter_mut() {
             im.during_big(&z, i);
         }
 
         // z = &z.square() + &c;
-        fractal.iterate_big(&mut z, &c);
+        fractal.iterate_big(&mut z, &c, &mut memory);
+        if fractal.can_detect_period() {
+            cycle.check(OrbitState::new(fractal, &z, &memory), |z1, z2| {
+                let difference = z1 - z2;
+                difference.real.square() + difference.im.square()
+            });
+        }
     }
 
     for im in implementations.iter_mut() {
+        if let Some(period) = cycle.period {
+            im.found_period(period);
+        }
         im.in_set_big(&z);
     }
-    true
+    SetRegion::InSet
+}
+
+/// returns if a perturbed pixel still needs deltas with an extended exponent,
+/// which is until dz fits in a double and dc either fits or is too small to change dz
+fn needs_extended_deltas(dz: ComplexExp, dc: ComplexExp) -> bool {
+    !dz.fits_double() || (!dc.fits_double() && dc.abs_squared() > dz.abs_squared() * (f64::EPSILON * f64::EPSILON))
 }
 
 /// analyse the given complex number, letting the implementors
 /// calculate their outputs
 /// 
-/// **using mandelbrot perturbation theory**
+/// **using perturbation theory**
+/// 
+/// julia sets are perturbed in the starting z instead of c,
+/// so the pixel's offset becomes the first dz
+/// 
+/// iterations are skipped with the bla table when it's given,
+/// as long as none of the implementors need to see every z
+/// and the orbit isn't being checked for a cycle
 /// 
 /// # Returns
-/// returns if the point is in the set or not
-fn diverges_implementors_big_perturbation_mandelbrot(
-    dc: Complex, ref_z: &Vec<Complex>, max_ref_iteration: usize, 
-    max_iterations: u32, bailout2: f64,
+/// returns where the point ended up, and whether the point glitched 
+/// (losing too much precision relative to the reference at some point)
+fn diverges_implementors_big_perturbation(
+    fractal: &Fractal, dc: ComplexExp, ref_z: &Vec<Complex>, max_ref_iteration: usize, 
+    bla: Option<&BlaTable>, max_iterations: u32, bailout2: f64,
     implementations: &mut Vec<LayerImplementation>
-) -> bool {
-    let mut dz = Complex::new(0., 0.);
+) -> (SetRegion, bool) {
+    let (mut dz, dc) = match fractal.is_julia() {
+        true => (dc, ComplexExp::ZERO),
+        false => (ComplexExp::ZERO, dc)
+    };
+    // julia orbits start from the pixel, so the first iterate
+    // is the second z checked when iterating normally
+    let first_iteration = fractal.is_julia() as u32;
     // https://fractalforums.org/fractal-mathematics-and-new-theories/28/another-solution-to-perturbation-glitches/4360/msg29835#msg29835
     let mut ref_iteration = 0;
+    // differences from the previous iteration
+    let mut memory = OrbitMemory::new_double();
 
     for im in implementations.iter_mut() {
-        im.before(max_iterations, bailout2);
+        im.before(fractal, max_iterations, bailout2);
     }
 
-    for i in 0..max_iterations {
-        // needs to change for fracal
-        dz =  ref_z[ref_iteration] * dz * 2. + dz.square() + dc;
+    if first_iteration == 1 && (ref_z[0] + dz.to_complex()).abs_squared() > bailout2 {
+        for im in implementations.iter_mut() {
+            im.out_set_double(ref_z[0] + dz.to_complex(), 0);
+        }
+        return (SetRegion::OutSet, false);
+    }
+
+    let mut glitched = false;
+    let mut i = first_iteration;
+    // deep zooms start with deltas too small for a double,
+    // so they're iterated with an extended exponent until they've grown
+    while i < max_iterations && needs_extended_deltas(dz, dc) {
+        dz = fractal.iterate_perturbed_extended(ref_z[ref_iteration], dz, dc);
         ref_iteration += 1;
 
+        let z2 = ref_z[ref_iteration] + dz.to_complex();
+
+        if z2.abs_squared() > bailout2 {
+            for im in implementations.iter_mut() {
+                im.out_set_double(z2, i);
+            }
+            return (SetRegion::OutSet, glitched);
+        }
+        let rebase = fractal.rebase_iteration(&memory, max_ref_iteration).map(|rebase_iteration| {
+            (rebase_iteration, ComplexExp::from_complex(ref_z[ref_iteration] - ref_z[rebase_iteration]) + dz)
+        });
+        let rebased = match rebase {
+            Some((rebase_iteration, rebased_dz)) 
+                if rebased_dz.abs_squared() < dz.abs_squared() || ref_iteration >= max_ref_iteration => {
+                ref_iteration = rebase_iteration;
+                dz = rebased_dz;
+                true
+            },
+            // the reference ran out with no iteration in phase to restart from
+            None if ref_iteration >= max_ref_iteration => {
+                for im in implementations.iter_mut() {
+                    im.in_set_double(z2);
+                }
+                return (SetRegion::InSet, true);
+            },
+            _ => false
+        };
+        // rebasing fixes the lost precision by restarting closer to z
+        if !rebased && z2.abs_squared() < GLITCH_TOLERANCE * ref_z[ref_iteration].abs_squared() {
+            glitched = true;
+        }
+
+        for im in implementations.iter_mut() {
+            im.during_double(z2, i);
+        }
+        i += 1;
+    }
+    let (mut dz, dc) = (dz.to_complex(), dc.to_complex());
+
+    // cycles are only found to within a double of the full z, 
+    // and only when every iteration is seen, so skipping is turned off for them
+    let detect_period = fractal.can_detect_period() && 
+        implementations.iter().any(|im| matches!(im, LayerImplementation::PeriodImplementor(_)));
+    let mut cycle = CycleDetector::new(PERIOD_EPSILON2, None);
+    let bla = bla.filter(|_| !detect_period && implementations.iter().all(|im| !im.needs_every_iteration()));
+
+    while i < max_iterations {
+        let previous_z = ref_z[ref_iteration] + dz;
+        match bla.and_then(|bla| bla.lookup(ref_iteration, dz, (max_iterations - i) as usize)) {
+            Some(step) => {
+                dz = step.apply(dz, dc);
+                ref_iteration += step.iterations();
+                i += step.iterations() as u32 - 1;
+            },
+            None => {
+                dz = fractal.iterate_perturbed(ref_z[ref_iteration], dz, dc, &mut memory);
+                ref_iteration += 1;
+            }
+        }
+
         let z2 = ref_z[ref_iteration] + dz;
 
         if z2.abs_squared() > bailout2 {
             for im in implementations.iter_mut() {
                 im.out_set_double(z2, i);
             }
-            return false;
+            return (SetRegion::OutSet, glitched);
+        }
+        let rebased = match fractal.rebase_iteration(&memory, max_ref_iteration) {
+            Some(rebase_iteration) 
+                if (z2 - ref_z[rebase_iteration]).abs_squared() < dz.abs_squared() || ref_iteration >= max_ref_iteration => {
+                ref_iteration = rebase_iteration;
+                dz = z2 - ref_z[ref_iteration];
+                // the reference orbit starts with no previous z,
+                // so the whole previous z becomes the difference
+                memory.previous_z = previous_z;
+                true
+            },
+            // the reference ran out with no iteration in phase to restart from
+            None if ref_iteration >= max_ref_iteration => {
+                for im in implementations.iter_mut() {
+                    im.in_set_double(z2);
+                }
+                return (SetRegion::InSet, true);
+            },
+            _ => false
+        };
+        // the difference cancelled out most of the reference,
+        // so z has far less precision than the reference did
+        // (unless rebasing has already restarted it from closer to z)
+        if !rebased && z2.abs_squared() < GLITCH_TOLERANCE * ref_z[ref_iteration].abs_squared() {
+            glitched = true;
         }
-        if z2.abs_squared() < dz.abs_squared() || ref_iteration == max_ref_iteration {
-            dz = z2;
-            ref_iteration = 0;
+
+        if detect_period {
+            let state = OrbitState { z: z2, previous_z, phase: fractal.orbit_phase(&memory) };
+            cycle.check(state, |z1, z2| (*z1 - *z2).abs_squared());
         }
 
         for im in implementations.iter_mut() {
             im.during_double(z2, i);
         }
+        i += 1;
     }
 
     for im in implementations.iter_mut() {
+        if let Some(period) = cycle.period {
+            im.found_period(period);
+        }
         im.in_set_double(ref_z[ref_iteration] + dz);
     }
 
-    true
+    (SetRegion::InSet, glitched)
 }
 
 #[derive(Clone)]
@@ -140,7 +448,10 @@ pub enum LayerType {
     ColourOrbitTrap(OrbitTrapType),
     Shading,
     Shading3D,
-    ShadingOrbitTrap(OrbitTrapType)
+    ShadingOrbitTrap(OrbitTrapType),
+    RootBasin,
+    Lyapunov,
+    Period
 }
 impl LayerType {
     pub fn get_string(&self) -> String{
@@ -149,7 +460,10 @@ impl LayerType {
             LayerType::ColourOrbitTrap(_) => "Colour (orbit trap)",
             LayerType::Shading => "Shading",
             LayerType::Shading3D => "Shading 3D",
-            LayerType::ShadingOrbitTrap(_) => "Shading (orbit trap)"
+            LayerType::ShadingOrbitTrap(_) => "Shading (orbit trap)",
+            LayerType::RootBasin => "Root basin",
+            LayerType::Lyapunov => "Lyapunov exponent",
+            LayerType::Period => "Period"
         })
     }
 
@@ -232,7 +546,10 @@ impl LayerType {
             LayerType::ColourOrbitTrap(_) => "1",
             LayerType::Shading => "2",
             LayerType::Shading3D => "3",
-            LayerType::ShadingOrbitTrap(_) => "4"
+            LayerType::ShadingOrbitTrap(_) => "4",
+            LayerType::RootBasin => "5",
+            LayerType::Lyapunov => "6",
+            LayerType::Period => "7"
         }
     }
 
@@ -251,6 +568,9 @@ impl LayerType {
             '2' => LayerType::Shading,
             '3' => LayerType::Shading3D,
             '4' => LayerType::ShadingOrbitTrap(OrbitTrapType::import_from_str(trap)),
+            '5' => LayerType::RootBasin,
+            '6' => LayerType::Lyapunov,
+            '7' => LayerType::Period,
             c => panic!("no layer type for {c}")
         }
     }
@@ -262,7 +582,10 @@ impl DropDownType<LayerType> for LayerType {
             LayerType::ColourOrbitTrap(LayerType::get_default_orbit_trap()),
             LayerType::Shading,
             LayerType::Shading3D,
-            LayerType::ShadingOrbitTrap(LayerType::get_default_orbit_trap())
+            LayerType::ShadingOrbitTrap(LayerType::get_default_orbit_trap()),
+            LayerType::RootBasin,
+            LayerType::Lyapunov,
+            LayerType::Period
         ]
     }
 
@@ -292,7 +615,10 @@ impl PartialEq for LayerType {
             LayerType::ShadingOrbitTrap(_) => match other {
                 LayerType::ShadingOrbitTrap(_) => true,
                 _ => false
-            }
+            },
+            LayerType::RootBasin => matches!(other, LayerType::RootBasin),
+            LayerType::Lyapunov => matches!(other, LayerType::Lyapunov),
+            LayerType::Period => matches!(other, LayerType::Period)
         }
     }
 }
@@ -301,7 +627,7 @@ impl PartialEq for LayerType {
 /// Controls the behaviour of layer implementors when analysing a point
 trait LayerImplementor {
     /// what needs to happen before iterations start for a given pixel
-    fn before(&mut self, max_iterations: u32, bailout2: f64);
+    fn before(&mut self, fractal: &Fractal, max_iterations: u32, bailout2: f64);
 
     /// what needs to happen each iteration, before the next z is calculated
     /// for double precision
@@ -317,6 +643,21 @@ trait LayerImplementor {
     /// for arbitrary precision
     fn out_set_big(&mut self, z: &BigComplex, i: u32);
 
+    /// what needs to happen if the point converged to a finite attractor
+    /// for double precision
+    fn converged_double(&mut self, z: Complex, i: u32) {
+        self.out_set_double(z, i);
+    }
+    /// what needs to happen if the point converged to a finite attractor
+    /// for arbitrary precision
+    fn converged_big(&mut self, z: &BigComplex, i: u32) {
+        self.out_set_big(z, i);
+    }
+
+    /// what needs to happen if the point's orbit fell into a cycle,
+    /// before it's found to be inside the set
+    fn found_period(&mut self, _period: u32) {}
+
     /// what needs to happen if the point is inside the set
     /// for double precision
     fn in_set_double(&mut self, z: Complex);
@@ -326,6 +667,24 @@ trait LayerImplementor {
 
     /// output the generated value
     fn get_output(&self) -> f64;
+
+    /// whether the implementor has to see z every iteration,
+    /// stopping iterations from being skipped
+    fn needs_every_iteration(&self) -> bool {
+        true
+    }
+
+    /// whether a region surrounded by points with the same output is 
+    /// known to have that output inside it, so it can be filled without iterating
+    fn can_guess(&self) -> bool {
+        false
+    }
+
+    /// whether a point reflected in the real axis (with a reflected orbit)
+    /// has the same output
+    fn is_symmetric(&self) -> bool {
+        false
+    }
 }
 
 // idk how to do polymorphism well so this is my best shot 
@@ -337,14 +696,20 @@ trait LayerImplementor {
 enum LayerImplementation {
     ColourImplemetor(ColourImplemetor),
     OrbitTrapImplementor(OrbitTrapImplementor),
-    Shading3DImplementor(Shading3DImplementor)
+    Shading3DImplementor(Shading3DImplementor),
+    RootBasinImplementor(RootBasinImplementor),
+    LyapunovImplementor(LyapunovImplementor),
+    PeriodImplementor(PeriodImplementor)
 }
 impl LayerImplementor for LayerImplementation {
-    fn before(&mut self, max_iterations: u32, bailout2: f64) {
+    fn before(&mut self, fractal: &Fractal, max_iterations: u32, bailout2: f64) {
         match self {
-            LayerImplementation::ColourImplemetor(im) => im.before(max_iterations, bailout2),
-            LayerImplementation::OrbitTrapImplementor(im) => im.before(max_iterations, bailout2),
-            LayerImplementation::Shading3DImplementor(im) => im.before(max_iterations, bailout2)
+            LayerImplementation::ColourImplemetor(im) => im.before(fractal, max_iterations, bailout2),
+            LayerImplementation::OrbitTrapImplementor(im) => im.before(fractal, max_iterations, bailout2),
+            LayerImplementation::Shading3DImplementor(im) => im.before(fractal, max_iterations, bailout2),
+            LayerImplementation::RootBasinImplementor(im) => im.before(fractal, max_iterations, bailout2),
+            LayerImplementation::LyapunovImplementor(im) => im.before(fractal, max_iterations, bailout2),
+            LayerImplementation::PeriodImplementor(im) => im.before(fractal, max_iterations, bailout2)
         }
     }
 
@@ -352,7 +717,10 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.during_double(z, i),
             LayerImplementation::OrbitTrapImplementor(im) => im.during_double(z, i),
-            LayerImplementation::Shading3DImplementor(im) => im.during_double(z, i)
+            LayerImplementation::Shading3DImplementor(im) => im.during_double(z, i),
+            LayerImplementation::RootBasinImplementor(im) => im.during_double(z, i),
+            LayerImplementation::LyapunovImplementor(im) => im.during_double(z, i),
+            LayerImplementation::PeriodImplementor(im) => im.during_double(z, i)
         }
     }
 
@@ -360,7 +728,10 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.during_big(z, i),
             LayerImplementation::OrbitTrapImplementor(im) => im.during_big(z, i),
-            LayerImplementation::Shading3DImplementor(im) => im.during_big(z, i)
+            LayerImplementation::Shading3DImplementor(im) => im.during_big(z, i),
+            LayerImplementation::RootBasinImplementor(im) => im.during_big(z, i),
+            LayerImplementation::LyapunovImplementor(im) => im.during_big(z, i),
+            LayerImplementation::PeriodImplementor(im) => im.during_big(z, i)
         }
     }
 
@@ -368,7 +739,10 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.out_set_double(z, i),
             LayerImplementation::OrbitTrapImplementor(im) => im.out_set_double(z, i),
-            LayerImplementation::Shading3DImplementor(im) => im.out_set_double(z, i)
+            LayerImplementation::Shading3DImplementor(im) => im.out_set_double(z, i),
+            LayerImplementation::RootBasinImplementor(im) => im.out_set_double(z, i),
+            LayerImplementation::LyapunovImplementor(im) => im.out_set_double(z, i),
+            LayerImplementation::PeriodImplementor(im) => im.out_set_double(z, i)
         }
     }
 
@@ -376,7 +750,43 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.out_set_big(z, i),
             LayerImplementation::OrbitTrapImplementor(im) => im.out_set_big(z, i),
-            LayerImplementation::Shading3DImplementor(im) => im.out_set_big(z, i)
+            LayerImplementation::Shading3DImplementor(im) => im.out_set_big(z, i),
+            LayerImplementation::RootBasinImplementor(im) => im.out_set_big(z, i),
+            LayerImplementation::LyapunovImplementor(im) => im.out_set_big(z, i),
+            LayerImplementation::PeriodImplementor(im) => im.out_set_big(z, i)
+        }
+    }
+
+    fn converged_double(&mut self, z: Complex, i: u32) {
+        match self {
+            LayerImplementation::ColourImplemetor(im) => im.converged_double(z, i),
+            LayerImplementation::OrbitTrapImplementor(im) => im.converged_double(z, i),
+            LayerImplementation::Shading3DImplementor(im) => im.converged_double(z, i),
+            LayerImplementation::RootBasinImplementor(im) => im.converged_double(z, i),
+            LayerImplementation::LyapunovImplementor(im) => im.converged_double(z, i),
+            LayerImplementation::PeriodImplementor(im) => im.converged_double(z, i)
+        }
+    }
+
+    fn converged_big(&mut self, z: &BigComplex, i: u32) {
+        match self {
+            LayerImplementation::ColourImplemetor(im) => im.converged_big(z, i),
+            LayerImplementation::OrbitTrapImplementor(im) => im.converged_big(z, i),
+            LayerImplementation::Shading3DImplementor(im) => im.converged_big(z, i),
+            LayerImplementation::RootBasinImplementor(im) => im.converged_big(z, i),
+            LayerImplementation::LyapunovImplementor(im) => im.converged_big(z, i),
+            LayerImplementation::PeriodImplementor(im) => im.converged_big(z, i)
+        }
+    }
+
+    fn found_period(&mut self, period: u32) {
+        match self {
+            LayerImplementation::ColourImplemetor(im) => im.found_period(period),
+            LayerImplementation::OrbitTrapImplementor(im) => im.found_period(period),
+            LayerImplementation::Shading3DImplementor(im) => im.found_period(period),
+            LayerImplementation::RootBasinImplementor(im) => im.found_period(period),
+            LayerImplementation::LyapunovImplementor(im) => im.found_period(period),
+            LayerImplementation::PeriodImplementor(im) => im.found_period(period)
         }
     }
 
@@ -384,7 +794,10 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.in_set_double(z),
             LayerImplementation::OrbitTrapImplementor(im) => im.in_set_double(z),
-            LayerImplementation::Shading3DImplementor(im) => im.in_set_double(z)
+            LayerImplementation::Shading3DImplementor(im) => im.in_set_double(z),
+            LayerImplementation::RootBasinImplementor(im) => im.in_set_double(z),
+            LayerImplementation::LyapunovImplementor(im) => im.in_set_double(z),
+            LayerImplementation::PeriodImplementor(im) => im.in_set_double(z)
         }
     }
 
@@ -392,7 +805,10 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.in_set_big(z),
             LayerImplementation::OrbitTrapImplementor(im) => im.in_set_big(z),
-            LayerImplementation::Shading3DImplementor(im) => im.in_set_big(z)
+            LayerImplementation::Shading3DImplementor(im) => im.in_set_big(z),
+            LayerImplementation::RootBasinImplementor(im) => im.in_set_big(z),
+            LayerImplementation::LyapunovImplementor(im) => im.in_set_big(z),
+            LayerImplementation::PeriodImplementor(im) => im.in_set_big(z)
         }
     }
 
@@ -400,7 +816,43 @@ impl LayerImplementor for LayerImplementation {
         match self {
             LayerImplementation::ColourImplemetor(im) => im.get_output(),
             LayerImplementation::OrbitTrapImplementor(im) => im.get_output(),
-            LayerImplementation::Shading3DImplementor(im) => im.get_output()
+            LayerImplementation::Shading3DImplementor(im) => im.get_output(),
+            LayerImplementation::RootBasinImplementor(im) => im.get_output(),
+            LayerImplementation::LyapunovImplementor(im) => im.get_output(),
+            LayerImplementation::PeriodImplementor(im) => im.get_output()
+        }
+    }
+
+    fn needs_every_iteration(&self) -> bool {
+        match self {
+            LayerImplementation::ColourImplemetor(im) => im.needs_every_iteration(),
+            LayerImplementation::OrbitTrapImplementor(im) => im.needs_every_iteration(),
+            LayerImplementation::Shading3DImplementor(im) => im.needs_every_iteration(),
+            LayerImplementation::RootBasinImplementor(im) => im.needs_every_iteration(),
+            LayerImplementation::LyapunovImplementor(im) => im.needs_every_iteration(),
+            LayerImplementation::PeriodImplementor(im) => im.needs_every_iteration()
+        }
+    }
+
+    fn can_guess(&self) -> bool {
+        match self {
+            LayerImplementation::ColourImplemetor(im) => im.can_guess(),
+            LayerImplementation::OrbitTrapImplementor(im) => im.can_guess(),
+            LayerImplementation::Shading3DImplementor(im) => im.can_guess(),
+            LayerImplementation::RootBasinImplementor(im) => im.can_guess(),
+            LayerImplementation::LyapunovImplementor(im) => im.can_guess(),
+            LayerImplementation::PeriodImplementor(im) => im.can_guess()
+        }
+    }
+
+    fn is_symmetric(&self) -> bool {
+        match self {
+            LayerImplementation::ColourImplemetor(im) => im.is_symmetric(),
+            LayerImplementation::OrbitTrapImplementor(im) => im.is_symmetric(),
+            LayerImplementation::Shading3DImplementor(im) => im.is_symmetric(),
+            LayerImplementation::RootBasinImplementor(im) => im.is_symmetric(),
+            LayerImplementation::LyapunovImplementor(im) => im.is_symmetric(),
+            LayerImplementation::PeriodImplementor(im) => im.is_symmetric()
         }
     }
 }
@@ -409,22 +861,26 @@ impl LayerImplementor for LayerImplementation {
 /// basic colour algorithm tracking the number of iterations a point took to diverge,
 /// calculating the smooth iteration
 struct ColourImplemetor {
-    output: f64
+    output: f64,
+    /// the power z is raised to each iteration
+    power: f64
 }
 impl ColourImplemetor {
     fn new() -> ColourImplemetor {
-        ColourImplemetor { output: 0.0 }
+        ColourImplemetor { output: 0.0, power: 2.0 }
     }
 
     fn out_set(&mut self, abs2_z: f64, i: u32) {
         let log_zmod = f64::log2(abs2_z) / 2.0;
-        let nu = f64::log2(log_zmod);
+        let nu = f64::log2(log_zmod) / f64::log2(self.power);
         let smooth_iteration = i as f64 + 1.0 - nu;
         self.output = smooth_iteration;
     }
 }
 impl LayerImplementor for ColourImplemetor {
-    fn before(&mut self, _max_iterations: u32, _bailout2: f64) {}
+    fn before(&mut self, fractal: &Fractal, _max_iterations: u32, _bailout2: f64) {
+        self.power = fractal.get_power();
+    }
 
     fn during_double(&mut self, _z: Complex, _i: u32) {}
     fn during_big(&mut self, _z: &BigComplex, _i: u32) {}
@@ -436,6 +892,109 @@ impl LayerImplementor for ColourImplemetor {
         self.out_set(z.abs_squared(), i);
     }
 
+    /// converged points use the iterations it took to converge
+    fn converged_double(&mut self, _z: Complex, i: u32) {
+        self.output = i as f64;
+    }
+    fn converged_big(&mut self, _z: &BigComplex, i: u32) {
+        self.output = i as f64;
+    }
+
+    fn in_set_double(&mut self, _z: Complex) {
+        self.output = 0.0;
+    }
+    fn in_set_big(&mut self, _z: &BigComplex) {
+        self.output = 0.0;
+    }
+
+    fn get_output(&self) -> f64 {
+        self.output
+    }
+
+    /// only the final z is used
+    fn needs_every_iteration(&self) -> bool {
+        false
+    }
+
+    /// the smooth iteration changes across any region which escapes,
+    /// so guessing relies on the points around a region having exactly the same output,
+    /// which only happens where it's constant (in the set, or converging in the same iterations)
+    fn can_guess(&self) -> bool {
+        true
+    }
+
+    /// only depends on |z|
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+/// root basin algorithm for fractals which converge to roots,
+/// calculating an index in the palette's band for the root reached,
+/// offset by the smooth number of iterations it took to converge
+struct RootBasinImplementor {
+    output: f64,
+    roots: Vec<Complex>,
+    /// size of the palette band given to each root
+    band: f64,
+    previous_z: Complex
+}
+impl RootBasinImplementor {
+    fn new() -> RootBasinImplementor {
+        RootBasinImplementor { 
+            output: 0.0, 
+            roots: Vec::new(), 
+            band: 0.0, 
+            previous_z: Complex::new(0.0, 0.0) 
+        }
+    }
+
+    fn out_set(&mut self, z: Complex, i: u32) {
+        let nearest = self.roots.iter().enumerate().min_by(|(_, r1), (_, r2)| {
+            (z - **r1).abs_squared().total_cmp(&(z - **r2).abs_squared())
+        });
+        let (root_i, root) = match nearest {
+            None => {
+                self.output = 0.0;
+                return;
+            },
+            Some((root_i, root)) => (root_i, *root)
+        };
+
+        // fraction of the last step needed to get within EPSILON of the root
+        let distance2 = (z - root).abs_squared().ln();
+        let previous_distance2 = (self.previous_z - root).abs_squared().ln();
+        let fraction = ((EPSILON.ln() - previous_distance2) / (distance2 - previous_distance2)).clamp(0.0, 1.0);
+        let smooth_iteration = match i {
+            0 => 0.0,
+            i => (i - 1) as f64 + fraction
+        };
+
+        self.output = root_i as f64 * self.band + smooth_iteration.min(self.band - 1.0);
+    }
+}
+impl LayerImplementor for RootBasinImplementor {
+    fn before(&mut self, fractal: &Fractal, max_iterations: u32, _bailout2: f64) {
+        self.roots.clear();
+        self.roots.extend_from_slice(fractal.get_roots());
+        self.band = max_iterations as f64 / self.roots.len().max(1) as f64;
+    }
+
+    fn during_double(&mut self, z: Complex, _i: u32) {
+        self.previous_z = z;
+    }
+    fn during_big(&mut self, z: &BigComplex, _i: u32) {
+        self.previous_z = z.to_complex();
+    }
+
+    fn out_set_double(&mut self, z: Complex, i: u32) {
+        self.out_set(z, i);
+    }
+    fn out_set_big(&mut self, z: &BigComplex, i: u32) {
+        self.out_set(z.to_complex(), i);
+    }
+
     fn in_set_double(&mut self, _z: Complex) {
         self.output = 0.0;
     }
@@ -448,6 +1007,114 @@ impl LayerImplementor for ColourImplemetor {
     }
 }
 
+#[derive(Clone)]
+/// lyapunov exponent algorithm for the lyapunov fractal,
+/// reading the exponent's sum from z and calculating an index in the palette
+/// which grows the more stable the point is (chaotic points are 0)
+struct LyapunovImplementor {
+    output: f64,
+    max_iterations: u32
+}
+impl LyapunovImplementor {
+    fn new() -> LyapunovImplementor {
+        LyapunovImplementor { output: 0.0, max_iterations: 0 }
+    }
+
+    fn in_set(&mut self, z: Complex) {
+        let measured = self.max_iterations.saturating_sub(LYAPUNOV_WARMUP).max(1);
+        let exponent = z.im / measured as f64;
+        self.output = match exponent < 0.0 {
+            true => self.max_iterations as f64 * (1.0 - exponent.exp()),
+            false => 0.0
+        };
+    }
+}
+impl LayerImplementor for LyapunovImplementor {
+    fn before(&mut self, _fractal: &Fractal, max_iterations: u32, _bailout2: f64) {
+        self.max_iterations = max_iterations;
+    }
+
+    fn during_double(&mut self, _z: Complex, _i: u32) {}
+    fn during_big(&mut self, _z: &BigComplex, _i: u32) {}
+
+    /// x escaped, so the exponent isn't defined
+    fn out_set_double(&mut self, _z: Complex, _i: u32) {
+        self.output = 0.0;
+    }
+    fn out_set_big(&mut self, _z: &BigComplex, _i: u32) {
+        self.output = 0.0;
+    }
+
+    fn in_set_double(&mut self, z: Complex) {
+        self.in_set(z);
+    }
+    fn in_set_big(&mut self, z: &BigComplex) {
+        self.in_set(z.to_complex());
+    }
+
+    fn get_output(&self) -> f64 {
+        self.output
+    }
+}
+
+#[derive(Clone)]
+/// period algorithm for points inside the set, 
+/// outputting the length of the cycle their orbit fell into
+/// (0 when no cycle was found)
+struct PeriodImplementor {
+    output: f64,
+    period: u32
+}
+impl PeriodImplementor {
+    fn new() -> PeriodImplementor {
+        PeriodImplementor { output: 0.0, period: 0 }
+    }
+}
+impl LayerImplementor for PeriodImplementor {
+    fn before(&mut self, _fractal: &Fractal, _max_iterations: u32, _bailout2: f64) {
+        self.period = 0;
+    }
+
+    fn during_double(&mut self, _z: Complex, _i: u32) {}
+    fn during_big(&mut self, _z: &BigComplex, _i: u32) {}
+
+    fn out_set_double(&mut self, _z: Complex, _i: u32) {
+        self.output = 0.0;
+    }
+    fn out_set_big(&mut self, _z: &BigComplex, _i: u32) {
+        self.output = 0.0;
+    }
+
+    fn found_period(&mut self, period: u32) {
+        self.period = period;
+    }
+
+    fn in_set_double(&mut self, _z: Complex) {
+        self.output = self.period as f64;
+    }
+    fn in_set_big(&mut self, _z: &BigComplex) {
+        self.output = self.period as f64;
+    }
+
+    fn get_output(&self) -> f64 {
+        self.output
+    }
+
+    /// only the cycle found is used
+    fn needs_every_iteration(&self) -> bool {
+        false
+    }
+
+    /// components with the same period have no holes
+    fn can_guess(&self) -> bool {
+        true
+    }
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Clone)]
 /// orbit trapped algorithm looking at the minimum distance between an orbit and a trap,
 /// calculating a trapped index to be used in the palette
@@ -492,7 +1159,7 @@ impl OrbitTrapImplementor {
     }
 }
 impl LayerImplementor for OrbitTrapImplementor {
-    fn before(&mut self, max_iterations: u32, bailout2: f64) {
+    fn before(&mut self, _fractal: &Fractal, max_iterations: u32, bailout2: f64) {
         self.min_distance2 = self.trap.greatest_distance2(bailout2);
         self.divisor = self.min_distance2.sqrt() / max_iterations as f64;
     }
@@ -531,6 +1198,12 @@ impl LayerImplementor for OrbitTrapImplementor {
     fn get_output(&self) -> f64 {
         self.output
     }
+
+    /// distances to a trap on the real axis are the same for reflected orbits,
+    /// but their angles are reversed
+    fn is_symmetric(&self) -> bool {
+        self.trap.get_center_im() == 0. && !matches!(self.trap.get_analysis(), OrbitTrapAnalysis::Angle)
+    }
 }
 
 #[derive(Clone)]
@@ -544,7 +1217,9 @@ struct Shading3DImplementor {
     der: Complex,
     der_big: BigComplex,
     dc: Complex,
-    dc_big: BigComplex
+    dc_big: BigComplex,
+    /// the power z is raised to each iteration
+    power: f64
 }
 impl Shading3DImplementor {
     fn new() -> Shading3DImplementor {
@@ -561,7 +1236,8 @@ impl Shading3DImplementor {
             der: Complex::new(1., 0.), 
             der_big: BigComplex::from_f64s(1., 0.),
             dc: Complex::new(1., 0.),
-            dc_big: BigComplex::from_f64s(1., 0.)
+            dc_big: BigComplex::from_f64s(1., 0.),
+            power: 2.
         }
     }
 
@@ -586,13 +1262,23 @@ impl Shading3DImplementor {
     }
 }
 impl LayerImplementor for Shading3DImplementor {
-    fn before(&mut self, _max_iterations: u32, _bailout2: f64) {}
+    fn before(&mut self, fractal: &Fractal, _max_iterations: u32, _bailout2: f64) {
+        self.power = fractal.get_power();
+    }
 
     fn during_double(&mut self, z: Complex, _i: u32) {
-        self.der = self.der * (z * 2.) + self.dc;
+        let dz = match self.power == 2. {
+            true => z * 2.,
+            false => z.powf(self.power - 1.) * self.power
+        };
+        self.der = self.der * dz + self.dc;
     }
     fn during_big(&mut self, z: &BigComplex, _i: u32) {
-        self.der_big = &self.der_big * (z * 2.) + &self.dc_big;
+        let dz = match self.power == 2. {
+            true => z * 2.,
+            false => z.powf(self.power - 1.) * self.power
+        };
+        self.der_big = &self.der_big * dz + &self.dc_big;
     }
 
     fn out_set_double(&mut self, z: Complex, _i: u32) {
@@ -623,10 +1309,13 @@ impl LayerImplementor for Shading3DImplementor {
 /// each layer to the index of the implementation it needs to use
 fn make_implementors(layers: &Vec<Layer>) -> (Vec<LayerImplementation>, Vec<usize>) {
     let mut implementors = Vec::new();
-    // colour and shading3D only need to be added once, so this
+    // colour, shading3D, root basin, lyapunov and period only need to be added once, so this
     // keeps track of the index of the implementors
     let mut colour_in: i16 = -1;
     let mut shading3d_in: i16 = -1;
+    let mut root_basin_in: i16 = -1;
+    let mut lyapunov_in: i16 = -1;
+    let mut period_in: i16 = -1;
 
     let mut implementor_map = Vec::with_capacity(layers.len());
     for layer in layers {
@@ -648,6 +1337,27 @@ fn make_implementors(layers: &Vec<Layer>) -> (Vec<LayerImplementation>, Vec<usiz
             LayerType::ColourOrbitTrap(trap) | LayerType::ShadingOrbitTrap(trap) => {
                 implementors.push(LayerImplementation::OrbitTrapImplementor(OrbitTrapImplementor::new(trap.clone())));
                 implementor_map.push(implementors.len()-1);
+            },
+            LayerType::RootBasin => {
+                if root_basin_in == -1 {
+                    implementors.push(LayerImplementation::RootBasinImplementor(RootBasinImplementor::new()));
+                    root_basin_in = (implementors.len()-1) as i16;
+                }
+                implementor_map.push(root_basin_in as usize);
+            },
+            LayerType::Lyapunov => {
+                if lyapunov_in == -1 {
+                    implementors.push(LayerImplementation::LyapunovImplementor(LyapunovImplementor::new()));
+                    lyapunov_in = (implementors.len()-1) as i16;
+                }
+                implementor_map.push(lyapunov_in as usize);
+            },
+            LayerType::Period => {
+                if period_in == -1 {
+                    implementors.push(LayerImplementation::PeriodImplementor(PeriodImplementor::new()));
+                    period_in = (implementors.len()-1) as i16;
+                }
+                implementor_map.push(period_in as usize);
             }
         }
     }
@@ -713,21 +1423,26 @@ impl Layers {
         // shading layers have to shade colours
         let mut non_shade_in_set = false;
         let mut non_shade_out_set = false;
+        let mut non_shade_converged = false;
         for layer in layers.iter() {
             if layer.layer_type.shading_layer() { 
-                if layer.layer_range.layer_covered(non_shade_in_set, non_shade_out_set) { continue }
+                if layer.layer_range.layer_covered(non_shade_in_set, non_shade_out_set, non_shade_converged) { continue }
                 return Err("Shading layer exists that isn't covered by another layer");
             }
             match layer.layer_range {
                 LayerRange::Both => {
                     non_shade_in_set = true;
                     non_shade_out_set = true;
+                    non_shade_converged = true;
                 },
                 LayerRange::InSet => {
                     non_shade_in_set = true;
                 },
                 LayerRange::OutSet => {
                     non_shade_out_set = true;
+                },
+                LayerRange::Converged => {
+                    non_shade_converged = true;
                 }
             }
         }
@@ -741,12 +1456,12 @@ impl Layers {
         }
     }
 
-    fn get_colours_before_first_shade(layers: &Vec<Layer>, first_shade_i: usize, in_set: bool) -> (usize, usize) {
+    fn get_colours_before_first_shade(layers: &Vec<Layer>, first_shade_i: usize, region: SetRegion) -> (usize, usize) {
         let mut first_colour_i = 0;
         let mut colours_before_first_shade = 0;
         for i in 0..first_shade_i {
             let layer = &layers[i];
-            if !layer.layer_type.shading_layer() && layer.layer_range.layer_applies(in_set) {
+            if !layer.layer_type.shading_layer() && layer.layer_range.layer_applies(region) {
                 colours_before_first_shade += 1;
                 if first_colour_i == 0 {
                     first_colour_i = i;
@@ -764,23 +1479,23 @@ impl Layers {
             layer.set_range_constraint(match layer.layer_range {
                 LayerRange::InSet => LayerRange::OutSet,
                 LayerRange::OutSet => LayerRange::InSet,
-                LayerRange::Both => LayerRange::OutSet
+                LayerRange::Both | LayerRange::Converged => LayerRange::OutSet
             });
             return;
         }
 
         let colour_in_set: usize = layers.iter().filter(|l| {
-            !l.layer_type.shading_layer() && l.layer_range.layer_applies(true)
+            !l.layer_type.shading_layer() && l.layer_range.layer_applies(SetRegion::InSet)
         }).collect::<Vec<&Layer>>().len();
         let colour_out_set: usize = layers.iter().filter(|l| {
-            !l.layer_type.shading_layer() && l.layer_range.layer_applies(false)
+            !l.layer_type.shading_layer() && l.layer_range.layer_applies(SetRegion::OutSet)
         }).collect::<Vec<&Layer>>().len();
         // force shading layers to stay in their ranges if no colour layer in other range
         for layer in layers.iter_mut() {
-            if colour_in_set == 0 && layer.layer_type.shading_layer() && layer.layer_range.layer_applies(false) {
+            if colour_in_set == 0 && layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::OutSet) {
                 layer.set_range_constraint(LayerRange::OutSet);
             }
-            if colour_out_set == 0 && layer.layer_type.shading_layer() && layer.layer_range.layer_applies(true) {
+            if colour_out_set == 0 && layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::InSet) {
                 layer.set_range_constraint(LayerRange::InSet);
             }
         }
@@ -788,24 +1503,24 @@ impl Layers {
         let mut first_shade_in_set: Option<usize> = None;
         let mut first_shade_out_set: Option<usize> = None;
         for (i, layer) in layers.iter().enumerate() {
-            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(true) && first_shade_in_set.is_none() {
+            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::InSet) && first_shade_in_set.is_none() {
                 first_shade_in_set = Some(i);
             }
-            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(false) && first_shade_out_set.is_none() {
+            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::OutSet) && first_shade_out_set.is_none() {
                 first_shade_out_set = Some(i);
             }
         }
 
         if let Some(first_shade_i) = first_shade_in_set {
             let (first_colour_i, colours_before_first_shade) = 
-                Layers::get_colours_before_first_shade(layers, first_shade_i, true);
+                Layers::get_colours_before_first_shade(layers, first_shade_i, SetRegion::InSet);
             if colours_before_first_shade == 1 {
                 layers[first_colour_i].set_range_constraint(LayerRange::InSet);
             }
         }   
         if let Some(first_shade_i) = first_shade_out_set {
             let (first_colour_i, colours_before_first_shade) = 
-                Layers::get_colours_before_first_shade(layers, first_shade_i, false);
+                Layers::get_colours_before_first_shade(layers, first_shade_i, SetRegion::OutSet);
             if colours_before_first_shade == 1 {
                 layers[first_colour_i].set_range_constraint(LayerRange::OutSet);
             }
@@ -821,21 +1536,21 @@ impl Layers {
         let mut first_colour_out_set: Option<usize> = None;
         for (i, layer) in layers.iter_mut().enumerate() {
             // find first colour layers
-            if !layer.layer_type.shading_layer() && layer.layer_range.layer_applies(true) && first_colour_in_set.is_none() {
+            if !layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::InSet) && first_colour_in_set.is_none() {
                 first_colour_in_set = Some(i);
             }
-            if !layer.layer_type.shading_layer() && layer.layer_range.layer_applies(false) && first_colour_out_set.is_none() {
+            if !layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::OutSet) && first_colour_out_set.is_none() {
                 first_colour_out_set = Some(i);
             }
 
             // set shading layers' constraints to never be before the first colour
-            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(true){
+            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::InSet){
                 layer.set_position_constraint(first_colour_in_set.unwrap()+1..end);
                 if first_shade_in_set.is_none() {
                     first_shade_in_set = Some(i);
                 }
             }
-            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(false){
+            if layer.layer_type.shading_layer() && layer.layer_range.layer_applies(SetRegion::OutSet){
                 layer.set_position_constraint(first_colour_out_set.unwrap()+1..end);
                 if first_shade_out_set.is_none() {
                     first_shade_out_set = Some(i);
@@ -847,12 +1562,12 @@ impl Layers {
         // a colour layer has to also be applied before
         if let Some(shade_i) = first_shade_in_set {
             layers.iter_mut().filter(|l| {
-                !l.layer_type.shading_layer() && l.layer_range.layer_applies(true)
+                !l.layer_type.shading_layer() && l.layer_range.layer_applies(SetRegion::InSet)
             }).collect::<Vec<&mut Layer>>().first_mut().unwrap().set_position_constraint(0..shade_i+1);
         }
         if let Some(shade_i) = first_shade_out_set {
             layers.iter_mut().filter(|l| {
-                !l.layer_type.shading_layer() && l.layer_range.layer_applies(false)
+                !l.layer_type.shading_layer() && l.layer_range.layer_applies(SetRegion::OutSet)
             }).collect::<Vec<&mut Layer>>().first_mut().unwrap().set_position_constraint(0..shade_i+1);
         }
     }
@@ -895,14 +1610,41 @@ impl Layers {
     pub fn change_layer_type(&mut self, index: usize, new_type: LayerType) {
         if !self.layers[index].can_change_type(&new_type) { return }
         let old_type = self.layers[index].layer_type.clone();
+        let old_range = self.layers[index].layer_range;
+        // root basins colour the points which reached a root
+        if matches!(new_type, LayerType::RootBasin) {
+            self.layers[index].layer_range = LayerRange::Converged;
+        }
+        // the exponent is only defined for points which stayed bounded
+        if matches!(new_type, LayerType::Lyapunov) {
+            self.layers[index].layer_range = LayerRange::InSet;
+        }
         self.layers[index].layer_type = new_type;
         if Layers::valid_layers(&self.layers).is_err() {
-            self.layers[index].layer_type = old_type
+            self.layers[index].layer_type = old_type;
+            self.layers[index].layer_range = old_range;
         }
 
+        Layers::place_constraints(&mut self.layers);
         self.update_implementors();
     }
 
+    /// switches the first layer to colour by the lyapunov exponent when the fractal needs it,
+    /// and back to escape time when it doesn't, as each fractal is flat with the other's layer
+    pub fn match_fractal(&mut self, fractal: &Fractal) {
+        let lyapunov = matches!(fractal, Fractal::Lyapunov(_));
+        for i in 0..self.layers.len() {
+            if !lyapunov && matches!(self.layers[i].layer_type, LayerType::Lyapunov) {
+                self.change_layer_type(i, LayerType::Colour);
+                self.layers[i].change_range(LayerRange::OutSet);
+            }
+        }
+        if lyapunov && !self.layers.iter().any(|layer| matches!(layer.layer_type, LayerType::Lyapunov)) {
+            self.change_layer_type(0, LayerType::Lyapunov);
+        }
+        Layers::place_constraints(&mut self.layers);
+    }
+
     /// makes sure all the palettes for the layers
     /// are updated for the current max iterations
     pub fn generate_palettes(&mut self, max_iterations: f32) {
@@ -914,45 +1656,72 @@ impl Layers {
     /// get the colour for the given complex number after passing 
     /// through all the layers
     pub fn colour_pixel(&self, fractal: &Fractal, c: ComplexType, max_iterations: u32, bailout2: f64) -> Color {
-        self.colour_pixel_implementors(fractal, c, max_iterations, bailout2)
+        self.colour_output(&self.point_output(fractal, c, max_iterations, bailout2))
     }
 
-    fn colour_pixel_implementors(&self, fractal: &Fractal, c: ComplexType, max_iterations: u32, bailout2: f64) -> Color {
+    /// iterate the given complex number, without colouring it
+    pub fn point_output(&self, fractal: &Fractal, c: ComplexType, max_iterations: u32, bailout2: f64) -> PointOutput {
         let mut implementors = self.implementors.clone();
-        let in_set = match c {
+        let region = match c {
             ComplexType::Double(c) => diverges_implementors_double(
                 fractal, c, max_iterations, bailout2, &mut implementors
             ),
+            ComplexType::DoubleDouble(c) => diverges_implementors_double_double(
+                fractal, c, max_iterations, bailout2, &mut implementors
+            ),
             ComplexType::Big(c) => diverges_implementors_big(
                 fractal, c, max_iterations, bailout2, &mut implementors
             )
         };
 
-        let mut colour: Option<Color> = None;
-        for (i, layer) in self.layers.iter().enumerate() {
-            let output = implementors[self.implementor_map[i]].get_output();
-            colour = layer.colour_implementors(colour, output, in_set);
-        }
+        PointOutput::new(region, &implementors)
+    }
 
-        match colour {
-            Some(c) => c,
-            None => BLACK
-        }
+    /// iterate the pixel offset by dc from the reference orbit, without colouring it,
+    /// returning whether the pixel glitched
+    pub fn point_output_perturbed(
+        &self, fractal: &Fractal,
+        dc: ComplexExp, ref_z: &Vec<Complex>, max_ref_iteration: usize, bla: Option<&BlaTable>,
+        max_iterations: u32, bailout2: f64
+    ) -> (PointOutput, bool) {
+        let mut implementors = self.implementors.clone();
+        let (region, glitched) = diverges_implementors_big_perturbation(
+            fractal, dc, ref_z, max_ref_iteration, bla, max_iterations, bailout2, &mut implementors);
+
+        (PointOutput::new(region, &implementors), glitched)
     }
 
+    /// get the colour for the pixel offset by dc from the reference orbit
+    /// after passing through all the layers, and whether the pixel glitched
     pub fn colour_pixel_implementors_perturbed(
-        &self, _fractal: &Fractal,
-        dc: Complex, ref_z: &Vec<Complex>, max_ref_iteration: usize, 
+        &self, fractal: &Fractal,
+        dc: ComplexExp, ref_z: &Vec<Complex>, max_ref_iteration: usize, bla: Option<&BlaTable>,
         max_iterations: u32, bailout2: f64
-    ) -> Color {
-        let mut implementors = self.implementors.clone();
-        let in_set = diverges_implementors_big_perturbation_mandelbrot(
-            dc, ref_z, max_ref_iteration, max_iterations, bailout2, &mut implementors);
+    ) -> (Color, bool) {
+        let (output, glitched) = self.point_output_perturbed(
+            fractal, dc, ref_z, max_ref_iteration, bla, max_iterations, bailout2);
+
+        (self.colour_output(&output), glitched)
+    }
+
+    /// pass an iterated point through all the layers
+    pub fn colour_output(&self, output: &PointOutput) -> Color {
+        self.colour_values(&output.outputs, output.region)
+    }
+
+    /// pass a point of a grid through all the layers
+    /// (points which haven't been rendered are black)
+    pub fn colour_point(&self, outputs: &PointOutputs, i: usize) -> Color {
+        match outputs.regions[i] {
+            Some(region) => self.colour_values(outputs.values(i), region),
+            None => BLACK
+        }
+    }
 
+    fn colour_values(&self, values: &[f64], region: SetRegion) -> Color {
         let mut colour: Option<Color> = None;
         for (i, layer) in self.layers.iter().enumerate() {
-            let output = implementors[self.implementor_map[i]].get_output();
-            colour = layer.colour_implementors(colour, output, in_set);
+            colour = layer.colour_implementors(colour, values[self.implementor_map[i]], region);
         }
 
         match colour {
@@ -961,6 +1730,18 @@ impl Layers {
         }
     }
 
+    /// whether every layer allows regions to be filled
+    /// from the points around them (see `LayerImplementor::can_guess`)
+    pub fn can_guess(&self) -> bool {
+        self.implementors.iter().all(|im| im.can_guess())
+    }
+
+    /// whether every layer colours points reflected in the real axis the same
+    /// (see `LayerImplementor::is_symmetric`)
+    pub fn is_symmetric(&self) -> bool {
+        self.implementors.iter().all(|im| im.is_symmetric())
+    }
+
     pub fn lerp_layers(layers1: &Layers, layers2: &Layers, percent: f64) -> Layers {
         let mut layers = Vec::new();
 
@@ -1013,24 +1794,31 @@ impl Layers {
 pub enum LayerRange {
     InSet,
     OutSet,
+    /// points which converged to a finite attractor
+    Converged,
+    /// every point
     Both
 }
 impl LayerRange {
-    /// returns whether they layer applies to a point in/out the set
-    pub fn layer_applies(&self, in_set: bool) -> bool {
+    /// returns whether they layer applies to a point in the given region
+    pub fn layer_applies(&self, region: SetRegion) -> bool {
         match self {
-            LayerRange::InSet => {return in_set},
-            LayerRange::OutSet => {return !in_set},
-            LayerRange::Both => {return true}
+            LayerRange::InSet => {region == SetRegion::InSet},
+            LayerRange::OutSet => {region == SetRegion::OutSet},
+            LayerRange::Converged => {region == SetRegion::Converged},
+            LayerRange::Both => {true}
         }
     }
 
     // returns whether the layer is covered by another layer already
-    fn layer_covered(&self, covered_in_set: bool, covered_out_set: bool) -> bool {
+    // (converged points are only checked for converged layers, as
+    // shading layers are skipped if nothing has been coloured before them)
+    fn layer_covered(&self, covered_in_set: bool, covered_out_set: bool, covered_converged: bool) -> bool {
         match self {
             LayerRange::Both => {covered_in_set && covered_out_set},
             LayerRange::InSet => {covered_in_set},
-            LayerRange::OutSet => {covered_out_set}
+            LayerRange::OutSet => {covered_out_set},
+            LayerRange::Converged => {covered_converged}
         }
     }
 
@@ -1038,7 +1826,8 @@ impl LayerRange {
         match self {
             LayerRange::InSet => "0",
             LayerRange::OutSet => "1",
-            LayerRange::Both => "2"
+            LayerRange::Both => "2",
+            LayerRange::Converged => "3"
         }
     }
 
@@ -1047,24 +1836,101 @@ impl LayerRange {
             '0' => LayerRange::InSet,
             '1' => LayerRange::OutSet,
             '2' => LayerRange::Both,
+            '3' => LayerRange::Converged,
             c => panic!("no layer range for {c}")
         }
     }
 }
 impl DropDownType<LayerRange> for LayerRange {
     fn get_variants() -> Vec<LayerRange> {
-        vec![LayerRange::InSet, LayerRange::OutSet, LayerRange::Both]
+        vec![LayerRange::InSet, LayerRange::OutSet, LayerRange::Converged, LayerRange::Both]
     }
 
     fn get_string(&self) -> String {
         String::from(match self {
             LayerRange::InSet => "In Set",
             LayerRange::OutSet => "Out Set",
-            LayerRange::Both => "Both"
+            LayerRange::Converged => "Converged",
+            LayerRange::Both => "All"
         })
     }
 }
 
+#[derive(Clone, PartialEq)]
+/// everything needed to colour an iterated point
+pub struct PointOutput {
+    region: SetRegion,
+    /// the output of each implementor
+    outputs: Vec<f64>
+}
+impl PointOutput {
+    fn new(region: SetRegion, implementors: &[LayerImplementation]) -> PointOutput {
+        PointOutput { region, outputs: implementors.iter().map(|im| im.get_output()).collect() }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+/// the outputs of a grid of points, stored flat 
+/// so they can be copied around without allocating for each point
+pub struct PointOutputs {
+    /// where each point ended up, or None if it hasn't been rendered
+    regions: Vec<Option<SetRegion>>,
+    /// the output of each implementor, for each point in turn
+    values: Vec<f64>,
+    /// the number of implementor outputs each point has
+    stride: usize
+}
+impl PointOutputs {
+    /// unrendered points, with room for the outputs of the given layers
+    pub fn new(points: usize, layers: &Layers) -> PointOutputs {
+        let stride = layers.implementors.len();
+        PointOutputs { regions: vec![None; points], values: vec![0.; points * stride], stride }
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// whether the points have room for the outputs of the given layers
+    pub fn fits(&self, layers: &Layers) -> bool {
+        self.stride == layers.implementors.len()
+    }
+
+    fn values(&self, i: usize) -> &[f64] {
+        &self.values[i * self.stride..(i + 1) * self.stride]
+    }
+
+    /// sets `len` points from the start to the given output
+    pub fn fill(&mut self, start: usize, len: usize, output: &PointOutput) {
+        self.regions[start..start + len].fill(Some(output.region));
+        for values in self.values[start * self.stride..(start + len) * self.stride].chunks_exact_mut(self.stride) {
+            values.copy_from_slice(&output.outputs);
+        }
+    }
+
+    /// copies `len` points to `start`, from `other_start` of points for the same layers
+    pub fn copy_from(&mut self, start: usize, other: &PointOutputs, other_start: usize, len: usize) {
+        self.regions[start..start + len].copy_from_slice(&other.regions[other_start..other_start + len]);
+        self.values[start * self.stride..(start + len) * self.stride]
+            .copy_from_slice(&other.values[other_start * other.stride..(other_start + len) * other.stride]);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+/// where a point ended up after iterating
+pub enum SetRegion {
+    /// never finished within the max iterations
+    InSet,
+    /// escaped past the bailout
+    OutSet,
+    /// converged to a finite attractor
+    Converged
+}
+
 /// A colouring layer for the mandelbrot set
 #[derive(Clone)]
 pub struct Layer {
@@ -1128,10 +1994,10 @@ impl Layer {
                 let mut in_set = false;
                 let mut out_set = false;
                 for layer in v {
-                    if layer.layer_applies(true) {
+                    if layer.layer_applies(SetRegion::InSet) {
                         in_set = true;
                     }
-                    if layer.layer_applies(false) {
+                    if layer.layer_applies(SetRegion::OutSet) {
                         out_set = true;
                     }
                 }
@@ -1140,11 +2006,11 @@ impl Layer {
                     (false, false) => true,
                     (true, false) => {
                         if self.layer_type.shading_layer() { range == LayerRange::InSet }
-                        else {range.layer_applies(true)}
+                        else {range.layer_applies(SetRegion::InSet)}
                     },
                     (false, true) => {
                         if self.layer_type.shading_layer() { range == LayerRange::OutSet }
-                        else {range.layer_applies(false)}
+                        else {range.layer_applies(SetRegion::OutSet)}
                     },
                     (true, true) => range == LayerRange::Both
                 }
@@ -1226,8 +2092,12 @@ impl Layer {
     }
 
     /// determine the new colour for the pixel, using the implementor's output
-    fn colour_implementors(&self, colour: Option<Color>, output: f64, in_set: bool) -> Option<Color> {
-        if !self.layer_range.layer_applies(in_set) {
+    fn colour_implementors(&self, colour: Option<Color>, output: f64, region: SetRegion) -> Option<Color> {
+        if !self.layer_range.layer_applies(region) {
+            return colour
+        }
+        // converged points may not have been coloured by any layer yet
+        if colour.is_none() && self.layer_type.shading_layer() {
             return colour
         }
 
@@ -1236,7 +2106,10 @@ impl Layer {
             LayerType::Shading => {self.shading(output, colour)},
             LayerType::ColourOrbitTrap(_) => {self.orbit_trap_colour(output)},
             LayerType::ShadingOrbitTrap(_) => {self.orbit_trap_shading(output, colour)},
-            LayerType::Shading3D => {self.shading_3d(output, colour)}
+            LayerType::Shading3D => {self.shading_3d(output, colour)},
+            LayerType::RootBasin => {self.colour(output)},
+            LayerType::Lyapunov => {self.colour(output)},
+            LayerType::Period => {self.colour(output)}
         };
 
         self.final_colour(colour, this_colour)
@@ -1316,4 +2189,130 @@ impl PartialEq for Layer {
             self.layer_range == other.layer_range &&
             self.palette.similar_palette(&other.palette)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interior_periods() {
+        let period = |fractal: &Fractal, c: Complex| {
+            let mut implementors = vec![
+                LayerImplementation::ColourImplemetor(ColourImplemetor::new()),
+                LayerImplementation::PeriodImplementor(PeriodImplementor::new())
+            ];
+            let region = diverges_implementors_double(fractal, c, 10000, BAILOUT, &mut implementors);
+            (region, implementors[0].get_output(), implementors[1].get_output())
+        };
+
+        assert!(period(&Fractal::Mandelbrot, Complex::new(0.1, 0.1)) == (SetRegion::InSet, 0., 1.));
+        assert!(period(&Fractal::Mandelbrot, Complex::new(-1.1, 0.1)) == (SetRegion::InSet, 0., 2.));
+        // the douady rabbit, found by cycle detection
+        assert!(period(&Fractal::Mandelbrot, Complex::new(-0.12, 0.75)) == (SetRegion::InSet, 0., 3.));
+        assert!(period(&Fractal::Julia(JuliaSeed::new(-0.12, 0.75)), Complex::new(0., 0.)).2 == 3.);
+        assert!(period(&Fractal::Mandelbrot, Complex::new(0.3, 0.)).0 == SetRegion::OutSet);
+
+        let c = BigComplex::from_f64s(-0.12, 0.75);
+        let mut implementors = vec![LayerImplementation::PeriodImplementor(PeriodImplementor::new())];
+        diverges_implementors_big(&Fractal::Mandelbrot, c.with_precision(64), 10000, BAILOUT, &mut implementors);
+        assert!(implementors[0].get_output() == 3.);
+        assert!(Fractal::Mandelbrot.known_interior_big(&BigComplex::from_f64s(-1.1, 0.1)) == Some(2));
+    }
+
+    #[test]
+    fn deep_and_perturbed_periods() {
+        // too precise for the squared distance to fit in a double
+        let c = BigComplex::from_f64s(-0.12, 0.75).with_precision(600);
+        let mut implementors = vec![LayerImplementation::PeriodImplementor(PeriodImplementor::new())];
+        diverges_implementors_big(&Fractal::Mandelbrot, c, 10000, BAILOUT, &mut implementors);
+        assert!(implementors[0].get_output() == 3.);
+
+        // the bla table is skipped, so every iteration is checked
+        let reference = ReferenceOrbit::new(
+            &Fractal::Mandelbrot, &BigComplex::from_f64s(-0.12, 0.75), 10000, BAILOUT, FloatExp::from(1e-9), 64
+        );
+        let mut implementors = vec![LayerImplementation::PeriodImplementor(PeriodImplementor::new())];
+        let (region, _) = diverges_implementors_big_perturbation(
+            &Fractal::Mandelbrot, ComplexExp::from_complex(Complex::new(1e-10, 0.)), &reference.ref_z,
+            reference.max_ref_iteration, reference.bla.as_ref(), 10000, BAILOUT, &mut implementors
+        );
+        assert!(region == SetRegion::InSet);
+        assert!(implementors[0].get_output() == 3.);
+    }
+
+    #[test]
+    fn cycles_compare_whole_state() {
+        let state = |z: f64, previous_z: f64, phase: u32| OrbitState {
+            z: Complex::new(z, 0.), previous_z: Complex::new(previous_z, 0.), phase
+        };
+        let distance2 = |z1: &Complex, z2: &Complex| (*z1 - *z2).abs_squared();
+        let mut cycle = CycleDetector::new(PERIOD_EPSILON2, None);
+
+        cycle.check(state(0.5, 0., 0), distance2);
+        // the same z from a different previous z or hybrid phase isn't a cycle
+        cycle.check(state(0.5, 0.2, 0), distance2);
+        cycle.check(state(0.5, 0.2, 1), distance2);
+        assert!(cycle.period.is_none());
+        cycle.check(state(0.5, 0.2, 0), distance2);
+        assert!(cycle.period == Some(2));
+    }
+
+    #[test]
+    fn guessing_layers() {
+        let period = Layer { layer_type: LayerType::Period, ..Layer::default() };
+        let shading = Layer { layer_type: LayerType::Shading3D, ..Layer::default() };
+
+        assert!(Layers::new(vec![Layer::default(), period], true).can_guess());
+        assert!(!Layers::new(vec![Layer::default(), shading], true).can_guess());
+    }
+
+    #[test]
+    fn root_basins_colour_converged_points() {
+        let colour = Layer { strength: 1., ..Layer::default() };
+        let mut layers = Layers::new(vec![colour], true);
+        layers.change_layer_type(0, LayerType::RootBasin);
+        assert!(layers.layers[0].layer_range == LayerRange::Converged);
+        layers.generate_palettes(100.);
+
+        let newton = Fractal::Newton(NewtonPolynomial::default());
+        let output = layers.point_output(&newton, ComplexType::Double(Complex::new(2., 0.5)), 100, 4.);
+        assert!(output.region == SetRegion::Converged);
+        assert!(layers.colour_output(&output) != BLACK);
+
+        layers.layers[0].change_range(LayerRange::OutSet);
+        assert!(layers.colour_output(&output) == BLACK);
+    }
+
+    #[test]
+    fn lyapunov_layers_follow_fractal() {
+        let colour = Layer { strength: 1., ..Layer::default() };
+        let mut layers = Layers::new(vec![colour], true);
+        let lyapunov = Fractal::Lyapunov(LyapunovSequence::default());
+        layers.match_fractal(&lyapunov);
+        assert!(matches!(layers.layers[0].layer_type, LayerType::Lyapunov));
+        assert!(layers.layers[0].layer_range == LayerRange::InSet);
+
+        // stable and chaotic points get different colours instead of a flat image
+        layers.generate_palettes(100.);
+        let stable = layers.point_output(&lyapunov, ComplexType::Double(Complex::new(2.5, 2.5)), 100, 4.);
+        let chaotic = layers.point_output(&lyapunov, ComplexType::Double(Complex::new(3.9, 3.9)), 100, 4.);
+        assert!(layers.colour_output(&stable) != layers.colour_output(&chaotic));
+
+        layers.match_fractal(&Fractal::Mandelbrot);
+        assert!(matches!(layers.layers[0].layer_type, LayerType::Colour));
+        assert!(layers.layers[0].layer_range == LayerRange::OutSet);
+    }
+
+    #[test]
+    fn symmetric_orbit_traps() {
+        let trap = |centre: (f64, f64), analysis: OrbitTrapAnalysis| {
+            LayerImplementation::OrbitTrapImplementor(OrbitTrapImplementor::new(
+                OrbitTrapType::Circle(OrbitTrapCircle::new(centre, 1., analysis))
+            ))
+        };
+
+        assert!(trap((-1., 0.), OrbitTrapAnalysis::Distance).is_symmetric());
+        assert!(!trap((-1., 0.5), OrbitTrapAnalysis::Distance).is_symmetric());
+        assert!(!trap((-1., 0.), OrbitTrapAnalysis::Angle).is_symmetric());
+    }
+}
@@ -6,10 +6,11 @@
 
 use macroquad::prelude::*;
 use chrono::format::strftime::StrftimeItems;
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicUsize, Ordering}};
 use std::thread;
 use std::time::Instant;
-use std::f64::consts::PI;
+use std::ops::Range;
+use std::f64::consts::{PI, LN_2};
 use std::fs;
 use ::rand::Rng;
 use threadpool::ThreadPool;
@@ -19,10 +20,18 @@ use num_cpus;
 pub mod complex;
 use complex::*;
 pub mod palettes;
+pub mod formula;
+use formula::Formula;
 pub mod layers;
-use layers::{Layer, Layers};
+pub mod bla;
+use bla::BlaTable;
+pub mod floatexp;
+use floatexp::{FloatExp, ComplexExp};
+pub mod doubledouble;
+use doubledouble::{ComplexDD, DoubleDouble};
+use layers::{Layer, Layers, SetRegion, PointOutput, PointOutputs};
 mod menu;
-use menu::Menu;
+use menu::{Menu, DropDownType};
 
 // width+height only used for the buhddabrot
 pub const WIDTH: usize = 600;
@@ -33,7 +42,32 @@ pub const BAILOUT: f64 = 4f64;
 pub const EPSILON: f64 = 1e-6f64;
 
 /// pixel step where anything smaller will use arbitrary precision
+/// (rendering with double-doubles until they're too imprecise)
 const ARB_PRECISION_THRESHOLD: f64 = 2e-16;
+/// pixel step where anything smaller is too precise for double-doubles
+const DOUBLE_DOUBLE_THRESHOLD: f64 = 1e-30;
+/// extra bits of arbitrary precision on top of what the pixel step needs,
+/// so rounding errors build up over the iterations without reaching the pixels
+const PRECISION_GUARD_BITS: usize = 32;
+
+/// how small a perturbed z's magnitude squared can get relative to the reference z's
+/// before too much precision has been lost (pauldelbrot's glitch criterion)
+pub const GLITCH_TOLERANCE: f64 = 1e-6;
+/// reference orbits made for each thread's glitched pixels before they're left as they are
+const MAX_SECONDARY_REFERENCES: usize = 10;
+/// how many times further than the corners of the screen a reference orbit
+/// is made valid for, so it can be reused while panning
+const REFERENCE_REACH: f64 = 2.;
+/// how many times further than needed a reused orbit's bla table can reach
+/// before it's remade, as its skips get shorter the further it has to be valid for
+const BLA_SHRINK_LIMIT: f64 = 4.;
+
+/// iterations used to find the roots of a newton fractal's polynomial
+const ROOT_FINDING_ITERATIONS: usize = 500;
+
+/// iterations of the logistic map done before
+/// the lyapunov exponent starts being measured
+pub const LYAPUNOV_WARMUP: u32 = 50;
 
 // 3D 
 pub const H2: f64 = 1.5;
@@ -45,11 +79,19 @@ pub const START_ZOOM_SPEED: f64 = 1f64;
 pub const MAX_ITER_INC_SPEED: f32 = 10f32;
 
 pub const THREADS: usize = 12; //12 14 15 17
+/// width and height of the tiles the image is rendered in, 
+/// in points (each colouring quality x quality pixels)
+const TILE_POINTS: usize = 32;
+/// how far (in pixels) the real axis can be from lining up with a row
+/// for the view to still be treated as symmetric
+const MIRROR_TOLERANCE: f64 = 1e-6;
 
 pub const MIN_FPS: usize = 10;
 /// extra fps that must be exceeded before the pixel size decreases
 pub const FPS_DROP_EXCESS: usize = (MIN_FPS as f32 * 0.75) as usize;
-pub const MAX_QUALITY: usize = 20;
+/// the biggest pixel block size, kept a power of 2 so each
+/// refinement pass halves it and lands on the same samples
+pub const MAX_QUALITY: usize = 16;
 /// if the percentage completed is above this value just after
 /// the thread pool has started, the pool will be joined
 pub const JOIN_PROGRESS_PERCENT: f32 = 0.05;
@@ -153,7 +195,7 @@ impl App {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct JuliaSeed {
     double: Complex,
     big: BigComplex
@@ -196,67 +238,1113 @@ impl JuliaSeed {
     }
 }
 
+#[derive(Clone, PartialEq)]
+/// polynomial used by the newton fractal, stored as its coefficients
+/// (highest power first) along with the roots it converges to
+pub struct NewtonPolynomial {
+    coefficients: Vec<f64>,
+    roots: Vec<Complex>
+}
+impl NewtonPolynomial {
+    /// # Errors
+    /// the polynomial has a degree less than 1
+    fn new(coefficients: Vec<f64>) -> Result<NewtonPolynomial, &'static str> {
+        let coefficients = match coefficients.iter().position(|c| *c != 0.) {
+            None => return Err("polynomial can't be 0"),
+            Some(first) => coefficients[first..].to_vec()
+        };
+        if coefficients.len() < 2 {
+            return Err("polynomial needs a degree of at least 1");
+        }
+
+        let roots = NewtonPolynomial::find_roots(&coefficients);
+        Ok(NewtonPolynomial { coefficients, roots })
+    }
+
+    /// returns p(z) and p'(z) for the given coefficients
+    fn horner(coefficients: &[f64], z: Complex) -> (Complex, Complex) {
+        let mut p = Complex::new(0., 0.);
+        let mut dp = Complex::new(0., 0.);
+        for coefficient in coefficients {
+            dp = dp * z + p;
+            p = p * z + Complex::new(*coefficient, 0.);
+        }
+        (p, dp)
+    }
+
+    /// finds all the roots of the polynomial using the durand-kerner method
+    fn find_roots(coefficients: &[f64]) -> Vec<Complex> {
+        let degree = coefficients.len() - 1;
+        let monic: Vec<f64> = coefficients.iter().map(|c| c / coefficients[0]).collect();
+
+        let start = Complex::new(0.4, 0.9);
+        let mut roots: Vec<Complex> = (0..degree).map(|i| start.pow(i as u32)).collect();
+        for _ in 0..ROOT_FINDING_ITERATIONS {
+            for i in 0..degree {
+                let mut denominator = Complex::new(1., 0.);
+                for j in 0..degree {
+                    if i != j {
+                        denominator = denominator * (roots[i] - roots[j]);
+                    }
+                }
+                roots[i] = roots[i] - NewtonPolynomial::horner(&monic, roots[i]).0 / denominator;
+            }
+        }
+
+        roots
+    }
+
+    /// returns p(z) and p'(z)
+    fn evaluate_double(&self, z: Complex) -> (Complex, Complex) {
+        NewtonPolynomial::horner(&self.coefficients, z)
+    }
+
+    /// returns p(z) and p'(z)
+    fn evaluate_double_double(&self, z: ComplexDD) -> (ComplexDD, ComplexDD) {
+        let mut p = ComplexDD::from_complex(Complex::new(0., 0.));
+        let mut dp = ComplexDD::from_complex(Complex::new(0., 0.));
+        for coefficient in self.coefficients.iter() {
+            dp = dp * z + p;
+            p = p * z + ComplexDD::from_complex(Complex::new(*coefficient, 0.));
+        }
+        (p, dp)
+    }
+
+    /// returns p(z) and p'(z)
+    fn evaluate_big(&self, z: &BigComplex) -> (BigComplex, BigComplex) {
+        let mut p = BigComplex::from_f64s(0., 0.);
+        let mut dp = BigComplex::from_f64s(0., 0.);
+        for coefficient in self.coefficients.iter() {
+            dp = &(&dp * z) + &p;
+            p = &(&p * z) + &BigComplex::from_f64s(*coefficient, 0.);
+        }
+        (p, dp)
+    }
+
+    /// returns the index of the root z has converged to, if any
+    pub fn converged_root(&self, z: Complex) -> Option<usize> {
+        self.roots.iter().position(|root| (z - *root).abs_squared() < EPSILON)
+    }
+
+    fn lerp_polynomials(poly1: &NewtonPolynomial, poly2: &NewtonPolynomial, percent: f64) -> NewtonPolynomial {
+        if poly1.coefficients.len() != poly2.coefficients.len() {
+            return poly2.clone();
+        }
+
+        let coefficients = poly1.coefficients.iter().zip(poly2.coefficients.iter())
+            .map(|(c1, c2)| lerpf64(*c1, *c2, percent))
+            .collect();
+        NewtonPolynomial::new(coefficients).unwrap_or(poly2.clone())
+    }
+
+    pub fn get_export_string(&self) -> String {
+        self.coefficients.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(",")
+    }
+
+    /// # Errors
+    /// the coefficients can't be parsed or don't make a valid polynomial
+    pub fn parse(coefficients: &str) -> Result<NewtonPolynomial, &'static str> {
+        let mut parsed = Vec::new();
+        for coefficient in coefficients.split(",") {
+            match coefficient.trim().parse::<f64>() {
+                Err(_) => return Err("unable to parse coefficient"),
+                Ok(c) => parsed.push(c)
+            }
+        }
+        NewtonPolynomial::new(parsed)
+    }
+}
+impl Default for NewtonPolynomial {
+    /// z^3 - 1
+    fn default() -> NewtonPolynomial {
+        NewtonPolynomial::new(vec![1., 0., 0., -1.]).unwrap()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+/// ordered list of formulas, each used for a number of iterations
+/// before moving onto the next, repeating after the last
+pub struct Hybrid {
+    steps: Vec<(Fractal, u32)>,
+    /// total iterations before the steps repeat
+    cycle: u32
+}
+impl Hybrid {
+    /// # Errors
+    /// there are no steps, a step is used for 0 iterations 
+    /// or a step's formula can't be used in a hybrid
+    fn new(steps: Vec<(Fractal, u32)>) -> Result<Hybrid, &'static str> {
+        if steps.is_empty() { return Err("hybrid needs at least 1 formula") }
+        if steps.iter().any(|(_, count)| *count == 0) { return Err("formulas need at least 1 iteration") }
+
+        let cycle = steps.iter().map(|(_, count)| count).sum();
+        Ok(Hybrid { steps, cycle })
+    }
+
+    /// returns the formula used for the given iteration
+    fn step(&self, iteration: u32) -> &Fractal {
+        let mut iteration = iteration % self.cycle;
+        for (fractal, count) in self.steps.iter() {
+            if iteration < *count { return fractal }
+            iteration -= count;
+        }
+        unreachable!("iteration is within the cycle")
+    }
+
+    /// returns if every step can be rendered with perturbation
+    /// 
+    /// (phoenix steps only keep their previous z between their own iterations,
+    /// which rebasing the reference orbit can't follow)
+    fn can_perturb(&self) -> bool {
+        self.steps.iter().all(|(fractal, _)| {
+            fractal.can_perturb() && !matches!(fractal, Fractal::Phoenix { .. })
+        })
+    }
+
+    pub fn get_export_string(&self) -> String {
+        self.steps.iter()
+            .map(|(fractal, count)| format!["{}*{}", fractal.get_export_string(), count])
+            .collect::<Vec<String>>().join("; ")
+    }
+
+    /// parses a formula usable as a step,
+    /// which are the escape time fractals over c
+    fn parse_step(step: &str) -> Result<Fractal, &'static str> {
+        let (name, params) = match step.split_once("(") {
+            None => (step, None),
+            Some((name, params)) => match params.strip_suffix(")") {
+                None => return Err("missing closing bracket"),
+                Some(params) => (name, Some(params))
+            }
+        };
+
+        match (name.trim(), params) {
+            ("Mandelbrot", None) => Ok(Fractal::Mandelbrot),
+            ("BurningShip", None) => Ok(Fractal::BurningShip),
+            ("Celtic", None) => Ok(Fractal::Celtic),
+            ("Buffalo", None) => Ok(Fractal::Buffalo),
+            ("Perpendicular", None) => Ok(Fractal::Perpendicular),
+            ("Tricorn", None) => Ok(Fractal::Tricorn),
+            ("Multibrot", Some(power)) => match power.trim().parse::<f64>() {
+                Err(_) => Err("unable to parse power"),
+                Ok(power) => Ok(Fractal::Multibrot { power })
+            },
+            ("Phoenix", Some(p)) => match p.split_once(",").map(|(re, im)| (re.trim().parse::<f64>(), im.trim().parse::<f64>())) {
+                Some((Ok(re), Ok(im))) => Ok(Fractal::Phoenix { p: JuliaSeed::new(re, im) }),
+                _ => Err("unable to parse phoenix parameter")
+            },
+            _ => Err("unknown formula")
+        }
+    }
+
+    /// parses steps separated by semicolons, 
+    /// each a formula optionally followed by *iterations
+    /// 
+    /// # Errors
+    /// a step can't be parsed or the steps don't make a valid hybrid
+    pub fn parse(sequence: &str) -> Result<Hybrid, &'static str> {
+        let mut steps = Vec::new();
+        for step in sequence.split(";").filter(|s| !s.trim().is_empty()) {
+            let (formula, count) = match step.rsplit_once("*") {
+                None => (step, 1),
+                Some((formula, count)) => match count.trim().parse::<u32>() {
+                    Err(_) => return Err("unable to parse iterations"),
+                    Ok(count) => (formula, count)
+                }
+            };
+            steps.push((Hybrid::parse_step(formula)?, count));
+        }
+        Hybrid::new(steps)
+    }
+}
+impl Default for Hybrid {
+    /// 2 mandelbrot iterations then 1 burning ship
+    fn default() -> Hybrid {
+        Hybrid::new(vec![(Fractal::Mandelbrot, 2), (Fractal::BurningShip, 1)]).unwrap()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+/// sequence of A and B choosing the growth rate used
+/// by each iteration of the logistic map, repeating after the last
+pub struct LyapunovSequence {
+    /// true where the sequence uses B
+    sequence: Vec<bool>
+}
+impl LyapunovSequence {
+    /// returns the growth rate for the given iteration,
+    /// where A is the real part of c and B the imaginary
+    fn rate(&self, iteration: u32, c: Complex) -> f64 {
+        match self.sequence[iteration as usize % self.sequence.len()] {
+            false => c.real,
+            true => c.im
+        }
+    }
+
+    pub fn get_export_string(&self) -> String {
+        self.sequence.iter().map(|b| if *b {'B'} else {'A'}).collect()
+    }
+
+    /// # Errors
+    /// the sequence is empty or contains something other than A or B
+    pub fn parse(sequence: &str) -> Result<LyapunovSequence, &'static str> {
+        let mut parsed = Vec::new();
+        for letter in sequence.trim().chars() {
+            match letter.to_ascii_uppercase() {
+                'A' => parsed.push(false),
+                'B' => parsed.push(true),
+                _ => return Err("sequence can only contain A and B")
+            }
+        }
+        if parsed.is_empty() { return Err("sequence needs at least 1 letter") }
+
+        Ok(LyapunovSequence { sequence: parsed })
+    }
+}
+impl Default for LyapunovSequence {
+    /// AB, alternating every iteration
+    fn default() -> LyapunovSequence {
+        LyapunovSequence::parse("AB").unwrap()
+    }
+}
+
 #[derive(Clone)]
+/// per-pixel state carried between iterations,
+/// for formulas which need more than the current z
+pub struct OrbitMemory<T> {
+    /// z from the previous iteration (0 before the first)
+    previous_z: T,
+    /// iterations done so far, used to pick the formula of a hybrid
+    /// or the growth rate of a lyapunov sequence
+    /// (only counted for those fractals)
+    iteration: u32
+}
+impl OrbitMemory<Complex> {
+    pub fn new_double() -> OrbitMemory<Complex> {
+        OrbitMemory { previous_z: Complex::new(0., 0.), iteration: 0 }
+    }
+}
+impl OrbitMemory<ComplexDD> {
+    pub fn new_double_double() -> OrbitMemory<ComplexDD> {
+        OrbitMemory { previous_z: ComplexDD::from_complex(Complex::new(0., 0.)), iteration: 0 }
+    }
+}
+impl OrbitMemory<BigComplex> {
+    pub fn new_big() -> OrbitMemory<BigComplex> {
+        OrbitMemory { previous_z: BigComplex::from_f64s(0., 0.), iteration: 0 }
+    }
+}
+
+#[derive(Clone, PartialEq)]
 pub enum Fractal {
     Mandelbrot,
-    Julia(JuliaSeed)
+    Julia(JuliaSeed),
+    /// z^power + c
+    Multibrot { power: f64 },
+    /// z^power + seed
+    MultiJulia { power: f64, seed: JuliaSeed },
+    /// (|re| + i|im|)^2 + c
+    BurningShip,
+    /// |re(z^2)| + i im(z^2) + c
+    Celtic,
+    /// |re(z^2)| - i|im(z^2)| + c
+    Buffalo,
+    /// (|re| - i im)^2 + c
+    Perpendicular,
+    /// conjugate(z)^2 + c
+    Tricorn,
+    /// conjugate(z)^2 + seed
+    TricornJulia(JuliaSeed),
+    /// part way between the mandelbrot/julia (amount 0) and
+    /// tricorn/tricorn julia (amount 1), used when animating between them
+    TricornBlend { amount: f64, seed: Option<JuliaSeed> },
+    /// z - p(z)/p'(z), converging to the roots of p
+    Newton(NewtonPolynomial),
+    /// z^2 + c + p * previous z
+    /// (p is stored like a julia seed, in double and arbitrary precision)
+    Phoenix { p: JuliaSeed },
+    /// ((z^2 + c - 1) / (2z + c - 2))^2, 
+    /// escaping or converging to 1
+    MagnetI,
+    /// ((z^3 + 3(c-1)z + (c-1)(c-2)) / (3z^2 + 3(c-2)z + (c-1)(c-2) + 1))^2,
+    /// escaping or converging to 1
+    MagnetII,
+    /// z - relaxation * p(z)/p'(z) + c, 
+    /// escaping or converging to a fixed point
+    Nova { poly: NewtonPolynomial, relaxation: f64 },
+    /// user entered formula for the next z
+    Custom(Formula),
+    /// alternates between formulas depending on the iteration
+    Hybrid(Hybrid),
+    /// logistic map x * r(1 - x), with r alternating between
+    /// A (re c) and B (im c) following the sequence
+    /// 
+    /// z holds x in the real part and the running sum 
+    /// of ln|r(1 - 2x)| in the imaginary part
+    Lyapunov(LyapunovSequence)
 }
 impl Fractal {
-    pub fn iterate_double(&self, z: &mut Complex, c: Complex) {
+    pub fn iterate_double(&self, z: &mut Complex, c: Complex, memory: &mut OrbitMemory<Complex>) {
+        match self {
+            Fractal::Mandelbrot => {*z = z.square() + c},
+            Fractal::Julia(seed) => {*z = z.square() + seed.double},
+            Fractal::Multibrot { power } => {*z = z.powf(*power) + c},
+            Fractal::MultiJulia { power, seed } => {*z = z.powf(*power) + seed.double},
+            Fractal::BurningShip => {*z = z.abs_real().abs_im().square() + c},
+            Fractal::Celtic => {*z = z.square().abs_real() + c},
+            Fractal::Buffalo => {*z = z.square().abs_real().abs_im().conjugate() + c},
+            Fractal::Perpendicular => {*z = z.abs_real().conjugate().square() + c},
+            Fractal::Tricorn => {*z = z.conjugate().square() + c},
+            Fractal::TricornJulia(seed) => {*z = z.conjugate().square() + seed.double},
+            Fractal::TricornBlend { amount, seed } => {
+                let z2 = z.square();
+                *z = Complex::new(z2.real, z2.im * (1. - 2.*amount)) + match seed {
+                    Some(seed) => seed.double,
+                    None => c
+                };
+            },
+            Fractal::Newton(poly) => {
+                let (p, dp) = poly.evaluate_double(*z);
+                // stop at stationary points instead of dividing by 0
+                if dp.abs_squared() == 0. { return }
+                *z = *z - p / dp;
+            },
+            Fractal::Phoenix { p } => {
+                let new_z = z.square() + c + p.double * memory.previous_z;
+                memory.previous_z = *z;
+                *z = new_z;
+            },
+            Fractal::MagnetI => {
+                let one = Complex::new(1., 0.);
+                let two = Complex::new(2., 0.);
+                let denominator = *z * 2. + c - two;
+                // stop at poles instead of dividing by 0
+                if denominator.abs_squared() == 0. { return }
+                *z = ((z.square() + c - one) / denominator).square();
+            },
+            Fractal::MagnetII => {
+                let c1 = c - Complex::new(1., 0.);
+                let c2 = c - Complex::new(2., 0.);
+                let numerator = z.square() * *z + c1 * *z * 3. + c1 * c2;
+                let denominator = z.square() * 3. + c2 * *z * 3. + c1 * c2 + Complex::new(1., 0.);
+                // stop at poles instead of dividing by 0
+                if denominator.abs_squared() == 0. { return }
+                *z = (numerator / denominator).square();
+            },
+            Fractal::Nova { poly, relaxation } => {
+                let (p, dp) = poly.evaluate_double(*z);
+                // stop at stationary points instead of dividing by 0
+                if dp.abs_squared() == 0. { return }
+                memory.previous_z = *z;
+                *z = *z - p / dp * *relaxation + c;
+            },
+            Fractal::Custom(formula) => {*z = formula.evaluate_double(*z, c)},
+            Fractal::Hybrid(hybrid) => {
+                let step = hybrid.step(memory.iteration);
+                memory.iteration += 1;
+                step.iterate_double(z, c, memory);
+            },
+            Fractal::Lyapunov(sequence) => {
+                let r = sequence.rate(memory.iteration, c);
+                let mut sum = z.im;
+                if memory.iteration >= LYAPUNOV_WARMUP {
+                    sum += (r * (1. - 2.*z.real)).abs().ln();
+                }
+                memory.iteration += 1;
+                *z = Complex::new(r * z.real * (1. - z.real), sum);
+            }
+        }
+    }
+
+    pub fn iterate_double_double(&self, z: &mut ComplexDD, c: ComplexDD, memory: &mut OrbitMemory<ComplexDD>) {
         match self {
             Fractal::Mandelbrot => {*z = z.square() + c},
-            Fractal::Julia(seed) => {*z = z.square() + seed.double}
+            Fractal::Julia(seed) => {*z = z.square() + ComplexDD::from_complex(seed.double)},
+            Fractal::Multibrot { power } => {*z = z.powf(*power) + c},
+            Fractal::MultiJulia { power, seed } => {*z = z.powf(*power) + ComplexDD::from_complex(seed.double)},
+            Fractal::BurningShip => {*z = z.abs_real().abs_im().square() + c},
+            Fractal::Celtic => {*z = z.square().abs_real() + c},
+            Fractal::Buffalo => {*z = z.square().abs_real().abs_im().conjugate() + c},
+            Fractal::Perpendicular => {*z = z.abs_real().conjugate().square() + c},
+            Fractal::Tricorn => {*z = z.conjugate().square() + c},
+            Fractal::TricornJulia(seed) => {*z = z.conjugate().square() + ComplexDD::from_complex(seed.double)},
+            Fractal::TricornBlend { amount, seed } => {
+                let z2 = z.square();
+                *z = ComplexDD::new(z2.real, z2.im * (1. - 2.*amount)) + match seed {
+                    Some(seed) => ComplexDD::from_complex(seed.double),
+                    None => c
+                };
+            },
+            Fractal::Newton(poly) => {
+                let (p, dp) = poly.evaluate_double_double(*z);
+                // stop at stationary points instead of dividing by 0
+                if dp.abs_squared() == 0. { return }
+                *z = *z - p / dp;
+            },
+            Fractal::Phoenix { p } => {
+                let new_z = z.square() + c + ComplexDD::from_complex(p.double) * memory.previous_z;
+                memory.previous_z = std::mem::replace(z, new_z);
+            },
+            Fractal::MagnetI => {
+                let one = ComplexDD::from_complex(Complex::new(1., 0.));
+                let two = ComplexDD::from_complex(Complex::new(2., 0.));
+                let denominator = *z * 2. + c - two;
+                // stop at poles instead of dividing by 0
+                if denominator.abs_squared() == 0. { return }
+                *z = ((z.square() + c - one) / denominator).square();
+            },
+            Fractal::MagnetII => {
+                let c1 = c - ComplexDD::from_complex(Complex::new(1., 0.));
+                let c2 = c - ComplexDD::from_complex(Complex::new(2., 0.));
+                let numerator = z.square() * *z + c1 * *z * 3. + c1 * c2;
+                let denominator = z.square() * 3. + c2 * *z * 3. + c1 * c2 + ComplexDD::from_complex(Complex::new(1., 0.));
+                // stop at poles instead of dividing by 0
+                if denominator.abs_squared() == 0. { return }
+                *z = (numerator / denominator).square();
+            },
+            Fractal::Nova { poly, relaxation } => {
+                let (p, dp) = poly.evaluate_double_double(*z);
+                // stop at stationary points instead of dividing by 0
+                if dp.abs_squared() == 0. { return }
+                let new_z = *z - p / dp * *relaxation + c;
+                memory.previous_z = std::mem::replace(z, new_z);
+            },
+            Fractal::Custom(formula) => {*z = formula.evaluate_double_double(*z, c)},
+            Fractal::Hybrid(hybrid) => {
+                let step = hybrid.step(memory.iteration);
+                memory.iteration += 1;
+                step.iterate_double_double(z, c, memory);
+            },
+            // the exponent only needs double precision, 
+            // as it's an average over the whole orbit
+            Fractal::Lyapunov(_) => {
+                let mut double_memory = OrbitMemory::new_double();
+                double_memory.iteration = memory.iteration;
+                let mut double_z = z.to_complex();
+                self.iterate_double(&mut double_z, c.to_complex(), &mut double_memory);
+                *z = ComplexDD::from_complex(double_z);
+                memory.iteration = double_memory.iteration;
+            }
         }
     }
 
-    pub fn iterate_big(&self, z: &mut BigComplex, c: &BigComplex) {
+    pub fn iterate_big(&self, z: &mut BigComplex, c: &BigComplex, memory: &mut OrbitMemory<BigComplex>) {
         match self {
             Fractal::Mandelbrot => {*z = &z.square() + c},
-            Fractal::Julia(seed) => {*z = &z.square() + &seed.big}
+            Fractal::Julia(seed) => {*z = &z.square() + &seed.big},
+            Fractal::Multibrot { power } => {*z = &z.powf(*power) + c},
+            Fractal::MultiJulia { power, seed } => {*z = &z.powf(*power) + &seed.big},
+            Fractal::BurningShip => {*z = &z.abs_real().abs_im().square() + c},
+            Fractal::Celtic => {*z = &z.square().abs_real() + c},
+            Fractal::Buffalo => {*z = &z.square().abs_real().abs_im().conjugate() + c},
+            Fractal::Perpendicular => {*z = &z.abs_real().conjugate().square() + c},
+            Fractal::Tricorn => {*z = &z.conjugate().square() + c},
+            Fractal::TricornJulia(seed) => {*z = &z.conjugate().square() + &seed.big},
+            Fractal::TricornBlend { amount, seed } => {
+                let z2 = z.square();
+                let im_scale = FBig::try_from(1. - 2.*amount).unwrap();
+                *z = &BigComplex::new(z2.real, z2.im * im_scale) + match seed {
+                    Some(seed) => &seed.big,
+                    None => c
+                };
+            },
+            Fractal::Newton(poly) => {
+                let (p, dp) = poly.evaluate_big(z);
+                // stop at stationary points instead of dividing by 0
+                if dp.abs_squared() == 0. { return }
+                *z = z.clone() - p / dp;
+            },
+            Fractal::Phoenix { p } => {
+                let new_z = &(&z.square() + c) + &(&p.big * &memory.previous_z);
+                memory.previous_z = std::mem::replace(z, new_z);
+            },
+            Fractal::MagnetI => {
+                let one = BigComplex::from_f64s(1., 0.);
+                let two = BigComplex::from_f64s(2., 0.);
+                let numerator = &(&z.square() + c) - &one;
+                let denominator = &(&(&*z * 2.) + c) - &two;
+                // stop at poles instead of dividing by 0
+                if denominator.abs_squared() == 0. { return }
+                *z = (numerator / denominator).square();
+            },
+            Fractal::MagnetII => {
+                let c1 = c - &BigComplex::from_f64s(1., 0.);
+                let c2 = c - &BigComplex::from_f64s(2., 0.);
+                let c1c2 = &c1 * &c2;
+                let z2 = z.square();
+                let numerator = &(&(&z2 * &*z) + &(&(&c1 * &*z) * 3.)) + &c1c2;
+                let denominator = &(&(&(&z2 * 3.) + &(&(&c2 * &*z) * 3.)) + &c1c2) + &BigComplex::from_f64s(1., 0.);
+                // stop at poles instead of dividing by 0
+                if denominator.abs_squared() == 0. { return }
+                *z = (numerator / denominator).square();
+            },
+            Fractal::Nova { poly, relaxation } => {
+                let (p, dp) = poly.evaluate_big(z);
+                // stop at stationary points instead of dividing by 0
+                if dp.abs_squared() == 0. { return }
+                let new_z = &(&*z - &(&(p / dp) * *relaxation)) + c;
+                memory.previous_z = std::mem::replace(z, new_z);
+            },
+            Fractal::Custom(formula) => {*z = formula.evaluate_big(z, c)},
+            Fractal::Hybrid(hybrid) => {
+                let step = hybrid.step(memory.iteration);
+                memory.iteration += 1;
+                step.iterate_big(z, c, memory);
+            },
+            // the exponent only needs double precision, 
+            // as it's an average over the whole orbit
+            Fractal::Lyapunov(_) => {
+                let mut double_memory = OrbitMemory::new_double();
+                double_memory.iteration = memory.iteration;
+                let mut double_z = z.to_complex();
+                self.iterate_double(&mut double_z, c.to_complex(), &mut double_memory);
+                *z = BigComplex::from_complex(double_z);
+                memory.iteration = double_memory.iteration;
+            }
         }
     }
 
-    pub fn is_mandelbrot(&self) -> bool {
+    /// returns the z iteration starts from for the given pixel
+    /// 
+    /// most formulas start at their critical point of 0 iterated once, which is c,
+    /// and newton fractals start at the pixel itself.
+    /// nova starts at 1 (a root of the default polynomial), the magnets start at 0,
+    /// and lyapunov sequences start x at 0.5
+    /// 
+    /// hybrids start from 0, so their first step does the first iteration
+    pub fn start_z_double(&self, c: Complex) -> Complex {
         match self {
-            Fractal::Mandelbrot => true,
+            Fractal::MagnetI | Fractal::MagnetII | Fractal::Hybrid(_) => Complex::new(0., 0.),
+            Fractal::Nova { .. } => Complex::new(1., 0.),
+            Fractal::Lyapunov(_) => Complex::new(0.5, 0.),
+            _ => c
+        }
+    }
+
+    /// returns the double-double z iteration starts from for the given pixel
+    /// (the same starting points as start_z_double)
+    /// 
+    /// double-doubles are used for fractals that can't be perturbed,
+    /// once pixels are too small for doubles but not too small for double-doubles.
+    /// the fixed starting points are exact as doubles, so they're made from doubles
+    pub fn start_z_double_double(&self, c: ComplexDD) -> ComplexDD {
+        ComplexDD::from_complex(match self {
+            Fractal::MagnetI | Fractal::MagnetII | Fractal::Hybrid(_) => Complex::new(0., 0.),
+            Fractal::Nova { .. } => Complex::new(1., 0.),
+            Fractal::Lyapunov(_) => Complex::new(0.5, 0.),
+            _ => return c
+        })
+    }
+
+    /// returns the arbitrary precision z iteration starts from for the given pixel
+    /// (the same starting points as start_z_double)
+    pub fn start_z_big(&self, c: &BigComplex) -> BigComplex {
+        match self {
+            Fractal::MagnetI | Fractal::MagnetII | Fractal::Hybrid(_) => BigComplex::from_f64s(0., 0.),
+            Fractal::Nova { .. } => BigComplex::from_f64s(1., 0.),
+            Fractal::Lyapunov(_) => BigComplex::from_f64s(0.5, 0.),
+            _ => c.clone()
+        }
+    }
+
+    /// returns where the point ended up if iteration has finished for it,
+    /// either escaping past the bailout or converging to a finite attractor
+    /// 
+    /// (newton fractals can't escape, only converge to a root)
+    /// 
+    /// (lyapunov fractals only escape if x does, as z's imaginary part is the exponent's sum)
+    pub fn finished_double(&self, z: Complex, memory: &OrbitMemory<Complex>, bailout2: f64) -> Option<SetRegion> {
+        let converged = match self {
+            Fractal::Newton(poly) => return match poly.converged_root(z) {
+                Some(_) => Some(SetRegion::Converged),
+                None => None
+            },
+            Fractal::Lyapunov(_) => return match z.real * z.real > bailout2 {
+                true => Some(SetRegion::OutSet),
+                false => None
+            },
+            Fractal::MagnetI | Fractal::MagnetII => (z - Complex::new(1., 0.)).abs_squared() < EPSILON,
+            Fractal::Nova { .. } => (z - memory.previous_z).abs_squared() < EPSILON,
             _ => false
+        };
+
+        if converged {
+            Some(SetRegion::Converged)
+        } else if z.abs_squared() > bailout2 {
+            Some(SetRegion::OutSet)
+        } else {
+            None
         }
     }
 
-    pub fn is_julia(&self) -> bool {
+    /// returns where the point ended up if iteration has finished for it,
+    /// either escaping past the bailout or converging to a finite attractor
+    /// 
+    /// (newton fractals can't escape, only converge to a root)
+    /// 
+    /// (lyapunov fractals only escape if x does, as z's imaginary part is the exponent's sum)
+    pub fn finished_big(&self, z: &BigComplex, memory: &OrbitMemory<BigComplex>, bailout2: f64) -> Option<SetRegion> {
+        let converged = match self {
+            Fractal::Newton(_) | Fractal::MagnetI | Fractal::MagnetII | Fractal::Lyapunov(_) => {
+                return self.finished_double(z.to_complex(), &OrbitMemory::new_double(), bailout2);
+            },
+            Fractal::Nova { .. } => (z - &memory.previous_z).abs_squared() < EPSILON,
+            _ => false
+        };
+
+        if converged {
+            Some(SetRegion::Converged)
+        } else if z.abs_squared() > bailout2 {
+            Some(SetRegion::OutSet)
+        } else {
+            None
+        }
+    }
+
+    /// returns where the point ended up if iteration has finished for it
+    /// 
+    /// (only sizes are checked, which don't need more than double precision)
+    pub fn finished_double_double(&self, z: ComplexDD, memory: &OrbitMemory<ComplexDD>, bailout2: f64) -> Option<SetRegion> {
+        let memory = OrbitMemory { previous_z: memory.previous_z.to_complex(), iteration: memory.iteration };
+        self.finished_double(z.to_complex(), &memory, bailout2)
+    }
+
+    /// returns the roots points converge to
+    /// (empty for escape time fractals)
+    pub fn get_roots(&self) -> &[Complex] {
+        match self {
+            Fractal::Newton(poly) => &poly.roots,
+            _ => &[]
+        }
+    }
+
+    /// returns if z is an orbit which can fall into a cycle
+    /// (the lyapunov fractal's z holds a running sum instead)
+    pub fn can_detect_period(&self) -> bool {
+        !matches!(self, Fractal::Lyapunov(_))
+    }
+
+    /// returns where the orbit is in the fractal's repeating formulas
+    /// (only hybrids change formula between iterations)
+    pub fn orbit_phase<T>(&self, memory: &OrbitMemory<T>) -> u32 {
+        match self {
+            Fractal::Hybrid(hybrid) => memory.iteration % hybrid.cycle,
+            _ => 0
+        }
+    }
+
+    /// returns if points reflected in the real axis have reflected orbits,
+    /// so the fractal is symmetric about it
+    pub fn is_symmetric(&self) -> bool {
+        match self {
+            Fractal::Mandelbrot | Fractal::Multibrot { .. } | Fractal::Tricorn => true,
+            Fractal::Julia(seed) | Fractal::MultiJulia { seed, .. } | Fractal::TricornJulia(seed) => seed.double.im == 0.,
+            _ => false
+        }
+    }
+
+    /// returns if the fractal raises z to a power which isn't a positive integer,
+    /// which is only calculated in double precision (see `BigComplex::powf`)
+    pub fn has_fractional_power(&self) -> bool {
         match self {
-            Fractal::Julia(_) => true,
+            Fractal::Multibrot { power } | Fractal::MultiJulia { power, .. } => power.fract() != 0. || *power < 0.,
+            Fractal::Hybrid(hybrid) => hybrid.steps.iter().any(|(fractal, _)| fractal.has_fractional_power()),
             _ => false
         }
     }
 
+    /// returns if the escape time can only change across the boundary of the set,
+    /// so regions surrounded by the same output can be filled in 
+    /// (true for polynomials, which have no holes in their escape regions)
+    pub fn can_guess(&self) -> bool {
+        match self {
+            Fractal::Mandelbrot | Fractal::Julia(_) => true,
+            Fractal::Multibrot { power } | Fractal::MultiJulia { power, .. } => power.fract() == 0. && *power >= 2.,
+            _ => false
+        }
+    }
+
+    /// returns the period of the mandelbrot set's main cardioid (1) or period 2 bulb
+    /// when c is inside them, so it's known to be in the set without iterating
+    pub fn known_interior_double(&self, c: Complex) -> Option<u32> {
+        if !matches!(self, Fractal::Mandelbrot) { return None }
+
+        let x = c.real - 0.25;
+        let y2 = c.im * c.im;
+        let q = x*x + y2;
+        if q * (q + x) <= y2 / 4. {
+            return Some(1);
+        }
+        if (c.real + 1.) * (c.real + 1.) + y2 <= 1. / 16. {
+            return Some(2);
+        }
+        None
+    }
+
+    /// returns the period of the mandelbrot set's main cardioid (1) or period 2 bulb
+    /// when c is inside them, so it's known to be in the set without iterating
+    pub fn known_interior_big(&self, c: &BigComplex) -> Option<u32> {
+        if !matches!(self, Fractal::Mandelbrot) { return None }
+
+        let quarter = FBig::try_from(0.25).unwrap();
+        let x = &c.real - &quarter;
+        let y2 = &c.im * &c.im;
+        let q = &x * &x + &y2;
+        if &q * (&q + &x) <= &y2 * &quarter {
+            return Some(1);
+        }
+        let x = &c.real + FBig::ONE;
+        if &x * &x + &y2 <= &quarter * &quarter {
+            return Some(2);
+        }
+        None
+    }
+
+    /// returns if the fractal can be rendered with perturbation
+    fn can_perturb(&self) -> bool {
+        match self {
+            Fractal::Hybrid(hybrid) => hybrid.can_perturb(),
+            _ => matches!(self, 
+                Fractal::Mandelbrot | Fractal::Julia(_) | Fractal::BurningShip | Fractal::Celtic |
+                Fractal::Buffalo | Fractal::Perpendicular | Fractal::Tricorn | Fractal::TricornJulia(_) |
+                Fractal::TricornBlend { .. } | Fractal::Phoenix { .. }
+            )
+        }
+    }
+
+    /// returns if the fractal can be perturbed with deltas 
+    /// too small for a double (only the mandelbrot and julia sets)
+    fn can_perturb_extended(&self) -> bool {
+        matches!(self, Fractal::Mandelbrot | Fractal::Julia(_))
+    }
+
+    /// returns if the fractal can be rendered with perturbation 
+    /// when pixels are the given distance apart
+    fn can_perturb_at(&self, pixel_step: FloatExp) -> bool {
+        self.can_perturb() && (pixel_step.fits_double() || self.can_perturb_extended())
+    }
+
+    /// returns the reference iteration a perturbed pixel restarts from when rebasing
+    /// 
+    /// hybrids restart where the reference is using the same formula as the pixel
+    /// (None if the reference escaped before getting there),
+    /// everything else restarts from the start of the reference orbit (0)
+    pub fn rebase_iteration(&self, memory: &OrbitMemory<Complex>, max_ref_iteration: usize) -> Option<usize> {
+        match self {
+            Fractal::Hybrid(hybrid) => {
+                let ref_iteration = (memory.iteration % hybrid.cycle) as usize;
+                (ref_iteration < max_ref_iteration).then_some(ref_iteration)
+            },
+            _ => Some(0)
+        }
+    }
+
+    /// iterates the difference between a pixel's orbit and the reference orbit,
+    /// given the reference z, the current difference dz and the pixel's offset dc
+    /// (julia sets are offset in the starting z instead, so dc is 0)
+    /// 
+    /// abs folds are handled piecewise with diff_abs, so deltas stay small
+    /// 
+    /// the memory holds the differences from the previous iteration
+    pub fn iterate_perturbed(&self, z: Complex, dz: Complex, dc: Complex, memory: &mut OrbitMemory<Complex>) -> Complex {
+        if let Fractal::Hybrid(hybrid) = self {
+            let step = hybrid.step(memory.iteration);
+            memory.iteration += 1;
+            return step.iterate_perturbed(z, dz, dc, memory);
+        }
+
+        let (x, y) = (z.real, z.im);
+        let (dx, dy) = (dz.real, dz.im);
+        // change in re(z^2) and im(z^2)/2
+        let re_change = (2.*x + dx)*dx - (2.*y + dy)*dy;
+        let im_change = x*dy + dx*y + dx*dy;
+        let dz = match self {
+            Fractal::BurningShip => Complex::new(
+                re_change,
+                2. * diff_abs(x*y, im_change)
+            ),
+            Fractal::Celtic => Complex::new(
+                diff_abs(x*x - y*y, re_change),
+                2. * im_change
+            ),
+            Fractal::Buffalo => Complex::new(
+                diff_abs(x*x - y*y, re_change),
+                -2. * diff_abs(x*y, im_change)
+            ),
+            Fractal::Perpendicular => Complex::new(
+                re_change,
+                -2. * (diff_abs(x, dx)*(y + dy) + x.abs()*dy)
+            ),
+            // conjugate(z+dz)^2 - conjugate(z)^2 = conjugate(2z*dz + dz^2)
+            Fractal::Tricorn | Fractal::TricornJulia(_) => Complex::new(re_change, -2. * im_change),
+            Fractal::TricornBlend { amount, .. } => Complex::new(
+                re_change,
+                2. * im_change * (1. - 2.*amount)
+            ),
+            Fractal::Phoenix { p } => {
+                let previous_dz = std::mem::replace(&mut memory.previous_z, dz);
+                z * dz * 2. + dz.square() + p.double * previous_dz
+            },
+            _ => z * dz * 2. + dz.square()
+        };
+
+        dz + dc
+    }
+
+    /// perturbed z^2 + c, for deltas beyond the range of a double
+    pub fn iterate_perturbed_extended(&self, z: Complex, dz: ComplexExp, dc: ComplexExp) -> ComplexExp {
+        dz * ComplexExp::from_complex(z * 2.) + dz.square() + dc
+    }
+
+    /// returns if the fractal is iterated over c (the mandelbrot plane)
+    pub fn is_mandelbrot(&self) -> bool {
+        match self {
+            Fractal::Mandelbrot | Fractal::Multibrot { .. } => true,
+            _ => false
+        }
+    }
+
+    /// returns if the fractal is iterated over z with a fixed seed (the julia plane)
+    pub fn is_julia(&self) -> bool {
+        self.julia_seed().is_some()
+    }
+
     pub fn unwrap_julia_seed(&mut self) -> &mut JuliaSeed {
         match self {
-            Fractal::Julia(seed) => seed,
+            Fractal::Julia(seed) | Fractal::MultiJulia { seed, .. } |
+            Fractal::TricornJulia(seed) | Fractal::TricornBlend { seed: Some(seed), .. } => seed,
             _ => panic!("not julia")
         }
     }
 
+    fn julia_seed(&self) -> Option<&JuliaSeed> {
+        match self {
+            Fractal::Julia(seed) | Fractal::MultiJulia { seed, .. } |
+            Fractal::TricornJulia(seed) | Fractal::TricornBlend { seed: Some(seed), .. } => Some(seed),
+            _ => None
+        }
+    }
+
+    /// returns how far the fractal is between the mandelbrot (0) and tricorn (1),
+    /// if it is part of either family
+    fn conjugate_amount(&self) -> Option<f64> {
+        match self {
+            Fractal::Mandelbrot | Fractal::Julia(_) => Some(0.),
+            Fractal::Tricorn | Fractal::TricornJulia(_) => Some(1.),
+            Fractal::TricornBlend { amount, .. } => Some(*amount),
+            _ => None
+        }
+    }
+
+    /// creates the fractal the given amount between the mandelbrot (0) and tricorn (1),
+    /// in the julia plane if a seed is given
+    fn conjugate_blend(amount: f64, seed: Option<JuliaSeed>) -> Fractal {
+        if amount != 0. && amount != 1. {
+            return Fractal::TricornBlend { amount, seed };
+        }
+
+        match (amount == 1., seed) {
+            (false, None) => Fractal::Mandelbrot,
+            (false, Some(seed)) => Fractal::Julia(seed),
+            (true, None) => Fractal::Tricorn,
+            (true, Some(seed)) => Fractal::TricornJulia(seed)
+        }
+    }
+
+    /// returns the power z is raised to each iteration
+    pub fn get_power(&self) -> f64 {
+        match self {
+            Fractal::Multibrot { power } | Fractal::MultiJulia { power, .. } => *power,
+            _ => 2.
+        }
+    }
+
+    /// returns if the fractal's power can be changed
+    fn has_power(&self) -> bool {
+        matches!(self, 
+            Fractal::Mandelbrot | Fractal::Julia(_) |
+            Fractal::Multibrot { .. } | Fractal::MultiJulia { .. }
+        )
+    }
+
+    /// changes the power z is raised to each iteration,
+    /// keeping the fractal in the same plane
+    /// 
+    /// (does nothing for fractals with a fixed power)
+    pub fn set_power(&mut self, power: f64) {
+        if !self.has_power() { return }
+
+        *self = match (self.julia_seed(), power == 2.) {
+            (None, true) => Fractal::Mandelbrot,
+            (None, false) => Fractal::Multibrot { power },
+            (Some(seed), true) => Fractal::Julia(seed.clone()),
+            (Some(seed), false) => Fractal::MultiJulia { power, seed: seed.clone() }
+        }
+    }
+
+    /// returns the mandelbrot plane version of this fractal
+    pub fn parameter_plane(&self) -> Fractal {
+        if let Some(amount) = self.conjugate_amount() {
+            return Fractal::conjugate_blend(amount, None);
+        }
+        if !self.has_power() { return self.clone() }
+
+        let mut fractal = Fractal::Mandelbrot;
+        fractal.set_power(self.get_power());
+        fractal
+    }
+
+    /// returns the julia plane version of this fractal with the given seed
+    pub fn julia_plane(&self, seed: JuliaSeed) -> Fractal {
+        if let Some(amount) = self.conjugate_amount() {
+            return Fractal::conjugate_blend(amount, Some(seed));
+        }
+
+        let mut fractal = Fractal::Julia(seed);
+        fractal.set_power(self.get_power());
+        fractal
+    }
+
+    /// returns the center the fractal is best viewed from
+    pub fn default_center(&self) -> Complex {
+        match self {
+            Fractal::MagnetI | Fractal::MagnetII => Complex::new(1.5, 0.),
+            Fractal::Newton(_) | Fractal::Nova { .. } => Complex::new(0., 0.),
+            Fractal::Lyapunov(_) => Complex::new(3., 3.),
+            fractal if fractal.is_julia() => Complex::new(0., 0.),
+            _ => Complex::new(-0.5, 0.)
+        }
+    }
+
     fn lerp_fractal(fractal1: &Fractal, fractal2: &Fractal, percent: f64) -> Fractal {
-        match fractal2 {
-            Fractal::Mandelbrot => Fractal::Mandelbrot,
-            Fractal::Julia(seed2) => match fractal1 {
-                Fractal::Mandelbrot => Fractal::Julia(seed2.clone()),
-                Fractal::Julia(seed1) => Fractal::Julia(JuliaSeed::lerp_seeds(seed1, seed2, percent))
+        let seed = match fractal2.julia_seed() {
+            None => None,
+            Some(seed2) => match fractal1.julia_seed() {
+                None => Some(seed2.clone()),
+                Some(seed1) => Some(JuliaSeed::lerp_seeds(seed1, seed2, percent))
             }
+        };
+
+        if fractal1.has_power() && fractal2.has_power() {
+            let mut fractal = match seed {
+                None => Fractal::Mandelbrot,
+                Some(seed) => Fractal::Julia(seed)
+            };
+            fractal.set_power(lerpf64(fractal1.get_power(), fractal2.get_power(), percent));
+            return fractal;
+        }
+
+        if let (Fractal::Newton(poly1), Fractal::Newton(poly2)) = (fractal1, fractal2) {
+            return Fractal::Newton(NewtonPolynomial::lerp_polynomials(poly1, poly2, percent));
+        }
+        if let (Fractal::Phoenix { p: p1 }, Fractal::Phoenix { p: p2 }) = (fractal1, fractal2) {
+            return Fractal::Phoenix { p: JuliaSeed::lerp_seeds(p1, p2, percent) };
+        }
+        if let (
+            Fractal::Nova { poly: poly1, relaxation: relaxation1 }, 
+            Fractal::Nova { poly: poly2, relaxation: relaxation2 }
+        ) = (fractal1, fractal2) {
+            return Fractal::Nova { 
+                poly: NewtonPolynomial::lerp_polynomials(poly1, poly2, percent), 
+                relaxation: lerpf64(*relaxation1, *relaxation2, percent) 
+            };
+        }
+
+        match (fractal1.conjugate_amount(), fractal2.conjugate_amount()) {
+            (Some(amount1), Some(amount2)) => Fractal::conjugate_blend(
+                lerpf64(amount1, amount2, percent), seed
+            ),
+            _ => fractal2.clone()
         }
     }
 
     fn get_export_string(&self) -> String {
         match self {
             Fractal::Mandelbrot => String::from("Mandelbrot"),
-            Fractal::Julia(seed) => format!["Julia({})", seed.get_export_string()]
+            Fractal::Julia(seed) => format!["Julia({})", seed.get_export_string()],
+            Fractal::Multibrot { power } => format!["Multibrot({})", power],
+            Fractal::MultiJulia { power, seed } => format!["MultiJulia({},{})", power, seed.get_export_string()],
+            Fractal::BurningShip => String::from("BurningShip"),
+            Fractal::Celtic => String::from("Celtic"),
+            Fractal::Buffalo => String::from("Buffalo"),
+            Fractal::Perpendicular => String::from("Perpendicular"),
+            Fractal::Tricorn => String::from("Tricorn"),
+            Fractal::TricornJulia(seed) => format!["TricornJulia({})", seed.get_export_string()],
+            Fractal::TricornBlend { amount, seed } => match seed {
+                None => format!["TricornBlend({})", amount],
+                Some(seed) => format!["TricornBlend({},{})", amount, seed.get_export_string()]
+            },
+            Fractal::Newton(poly) => format!["Newton({})", poly.get_export_string()],
+            Fractal::Phoenix { p } => format!["Phoenix({})", p.get_export_string()],
+            Fractal::MagnetI => String::from("MagnetI"),
+            Fractal::MagnetII => String::from("MagnetII"),
+            Fractal::Nova { poly, relaxation } => format!["Nova({};{})", relaxation, poly.get_export_string()],
+            Fractal::Custom(formula) => format!["Custom({})", formula.get_text()],
+            Fractal::Hybrid(hybrid) => format!["Hybrid({})", hybrid.get_export_string()],
+            Fractal::Lyapunov(sequence) => format!["Lyapunov({})", sequence.get_export_string()]
         }
     }
 
     fn import_from_str(fractal: &str) -> Fractal {
-        if fractal == "Mandelbrot" {
-            return Fractal::Mandelbrot;
+        match fractal {
+            "Mandelbrot" => return Fractal::Mandelbrot,
+            "BurningShip" => return Fractal::BurningShip,
+            "Celtic" => return Fractal::Celtic,
+            "Buffalo" => return Fractal::Buffalo,
+            "Perpendicular" => return Fractal::Perpendicular,
+            "Tricorn" => return Fractal::Tricorn,
+            "MagnetI" => return Fractal::MagnetI,
+            "MagnetII" => return Fractal::MagnetII,
+            _ => {}
+        }
+        // the formula can contain brackets, so everything up to the last bracket is used
+        if let Some(formula) = fractal.strip_prefix("Custom(").and_then(|f| f.strip_suffix(")")) {
+            return Fractal::Custom(Formula::parse(formula).unwrap());
+        }
+        // steps can have their own brackets
+        if let Some(sequence) = fractal.strip_prefix("Hybrid(").and_then(|f| f.strip_suffix(")")) {
+            return Fractal::Hybrid(Hybrid::parse(sequence).unwrap());
+        }
+        if fractal.starts_with("Lyapunov") {
+            return Fractal::Lyapunov(LyapunovSequence::parse(get_str_between(fractal, "(", ")")).unwrap());
+        }
+        if fractal.starts_with("Nova") {
+            let (relaxation, poly) = get_str_between(fractal, "(", ")").split_once(";").unwrap();
+            return Fractal::Nova { 
+                poly: NewtonPolynomial::parse(poly).unwrap(), 
+                relaxation: relaxation.parse::<f64>().unwrap() 
+            };
+        }
+        if fractal.starts_with("Phoenix") {
+            return Fractal::Phoenix { p: JuliaSeed::import_from_str(get_str_between(fractal, "(", ")")) };
+        }
+        if fractal.starts_with("Newton") {
+            return Fractal::Newton(NewtonPolynomial::parse(get_str_between(fractal, "(", ")")).unwrap());
+        }
+        if fractal.starts_with("TricornJulia") {
+            return Fractal::TricornJulia(JuliaSeed::import_from_str(get_str_between(fractal, "(", ")")));
+        }
+        if fractal.starts_with("TricornBlend") {
+            let params = get_str_between(fractal, "(", ")");
+            return match params.split_once(",") {
+                None => Fractal::TricornBlend { amount: params.parse::<f64>().unwrap(), seed: None },
+                Some((amount, seed)) => Fractal::TricornBlend { 
+                    amount: amount.parse::<f64>().unwrap(), 
+                    seed: Some(JuliaSeed::import_from_str(seed)) 
+                }
+            };
+        }
+        if fractal.starts_with("Multibrot") {
+            return Fractal::Multibrot { 
+                power: get_str_between(fractal, "(", ")").parse::<f64>().unwrap() 
+            };
+        }
+        if fractal.starts_with("MultiJulia") {
+            let (power, seed) = get_str_between(fractal, "(", ")").split_once(",").unwrap();
+            return Fractal::MultiJulia { 
+                power: power.parse::<f64>().unwrap(), 
+                seed: JuliaSeed::import_from_str(seed) 
+            };
         }
         if &fractal[0..=4] == "Julia" {
             return Fractal::Julia(JuliaSeed::import_from_str(get_str_between(fractal, "(", ")")));
@@ -265,6 +1353,93 @@ impl Fractal {
         panic!("unknown fractal")
     }
 }
+impl DropDownType<Fractal> for Fractal {
+    fn get_variants() -> Vec<Fractal> {
+        vec![
+            Fractal::Mandelbrot,
+            Fractal::Julia(JuliaSeed::new(0., 0.)),
+            Fractal::BurningShip,
+            Fractal::Celtic,
+            Fractal::Buffalo,
+            Fractal::Perpendicular,
+            Fractal::Tricorn,
+            Fractal::TricornJulia(JuliaSeed::new(0., 0.)),
+            Fractal::Newton(NewtonPolynomial::default()),
+            Fractal::Phoenix { p: JuliaSeed::new(-0.5, 0.) },
+            Fractal::MagnetI,
+            Fractal::MagnetII,
+            Fractal::Nova { poly: NewtonPolynomial::default(), relaxation: 1. },
+            Fractal::Custom(Formula::default()),
+            Fractal::Hybrid(Hybrid::default()),
+            Fractal::Lyapunov(LyapunovSequence::default())
+        ]
+    }
+
+    fn get_string(&self) -> String {
+        String::from(match self {
+            Fractal::Mandelbrot => "Mandelbrot",
+            Fractal::Julia(_) => "Julia",
+            Fractal::Multibrot { .. } => "Multibrot",
+            Fractal::MultiJulia { .. } => "Multi-Julia",
+            Fractal::BurningShip => "Burning Ship",
+            Fractal::Celtic => "Celtic",
+            Fractal::Buffalo => "Buffalo",
+            Fractal::Perpendicular => "Perpendicular",
+            Fractal::Tricorn => "Tricorn",
+            Fractal::TricornJulia(_) => "Tricorn Julia",
+            Fractal::TricornBlend { .. } => "Tricorn (blend)",
+            Fractal::Newton(_) => "Newton",
+            Fractal::Phoenix { .. } => "Phoenix",
+            Fractal::MagnetI => "Magnet I",
+            Fractal::MagnetII => "Magnet II",
+            Fractal::Nova { .. } => "Nova",
+            Fractal::Custom(_) => "Custom",
+            Fractal::Hybrid(_) => "Hybrid",
+            Fractal::Lyapunov(_) => "Lyapunov"
+        })
+    }
+
+    /// the variants have default parameters, so match any parameters
+    fn same_variant(&self, variant: &Fractal) -> bool {
+        self.same_family(variant)
+    }
+}
+impl Fractal {
+    /// whether the fractals use the same formula,
+    /// (ignoring the power and julia seed)
+    pub fn same_family(&self, other: &Fractal) -> bool {
+        match self {
+            Fractal::Mandelbrot | Fractal::Multibrot { .. } => other.is_mandelbrot(),
+            Fractal::Julia(_) | Fractal::MultiJulia { .. } => other.is_julia(),
+            Fractal::BurningShip => matches!(other, Fractal::BurningShip),
+            Fractal::Celtic => matches!(other, Fractal::Celtic),
+            Fractal::Buffalo => matches!(other, Fractal::Buffalo),
+            Fractal::Perpendicular => matches!(other, Fractal::Perpendicular),
+            Fractal::Tricorn | Fractal::TricornJulia(_) | Fractal::TricornBlend { .. } => {
+                other.conjugate_amount().is_some() && !other.has_power() && 
+                    self.is_julia() == other.is_julia()
+            },
+            Fractal::Newton(_) => matches!(other, Fractal::Newton(_)),
+            Fractal::Phoenix { .. } => matches!(other, Fractal::Phoenix { .. }),
+            Fractal::MagnetI => matches!(other, Fractal::MagnetI),
+            Fractal::MagnetII => matches!(other, Fractal::MagnetII),
+            Fractal::Nova { .. } => matches!(other, Fractal::Nova { .. }),
+            Fractal::Custom(_) => matches!(other, Fractal::Custom(_)),
+            Fractal::Hybrid(_) => matches!(other, Fractal::Hybrid(_)),
+            Fractal::Lyapunov(_) => matches!(other, Fractal::Lyapunov(_))
+        }
+    }
+}
+
+/// returns |c+d| - |c| without losing precision when d is small
+fn diff_abs(c: f64, d: f64) -> f64 {
+    match (c >= 0., c + d >= 0.) {
+        (true, true) => d,
+        (true, false) => -(2.*c + d),
+        (false, true) => 2.*c + d,
+        (false, false) => -d
+    }
+}
 
 pub mod orbit_trap {
     use std::f64::consts::PI;
@@ -862,8 +2037,8 @@ fn lerpf64(a: f64, b: f64, t: f64) -> f64 {
     }
 }
 
-fn lerpf64_ln(a: f64, b: f64, t: f64) -> f64 {
-    lerpf64(a.ln(), b.ln(), t).exp()
+fn lerp_floatexp_ln(a: FloatExp, b: FloatExp, t: f64) -> FloatExp {
+    FloatExp::exp(lerpf64(a.ln(), b.ln(), t))
 }
 
 // fn lerpf64_mexp(a: f64, b: f64, t: f64, m: f64) -> f64 {
@@ -881,11 +2056,6 @@ fn lerp_fbig(a: FBig, b: FBig, t: &FBig) -> FBig {
     (FBig::ONE - t) * a + t * b
 }
 
-#[allow(unused)] // will be used once pixel step can become arbitrary
-fn lerp_fbig_ln(a: FBig, b: FBig, t: &FBig) -> FBig {
-    FBig::exp(&lerp_fbig(a.ln(), b.ln(), &t))
-}
-
 // fn lerp_fbig_mexp(a: FBig, b: FBig, t: &FBig, m: &FBig) -> FBig {
 //     lerp_fbig((m * &a).exp(), (m * &b).exp(), t).ln() / m
 // }
@@ -941,193 +2111,665 @@ fn complex_to_screen(dimensions: ScreenDimensions, pixel_step: f64, center: (f64
     Some((x as usize, y as usize))
 }
 
-struct ThreadSplitter {
-    x_excess: usize,
-    y_excess: usize,
-    x_end: usize, 
-    y_end: usize
+#[derive(Clone, Debug, PartialEq)]
+/// a rectangle of the image, rendered as one piece of work
+pub(crate) struct Tile {
+    pub(crate) x: usize,
+    pub(crate) y: usize,
+    pub(crate) width: usize,
+    pub(crate) height: usize
+}
+impl Tile {
+    /// splits the given areas of the image into tiles, ordered so the ones 
+    /// nearest the centre are at the end (taken first)
+    fn make_tiles(dimensions: &ScreenDimensions, areas: &[Tile], quality: usize) -> Vec<Tile> {
+        let size = TILE_POINTS * quality;
+        let mut tiles = Vec::new();
+        for area in areas {
+            for y in (area.y..area.y+area.height).step_by(size) {
+                for x in (area.x..area.x+area.width).step_by(size) {
+                    tiles.push(Tile { 
+                        x, y, 
+                        width: size.min(area.x + area.width - x), 
+                        height: size.min(area.y + area.height - y) 
+                    });
+                }
+            }
+        }
+
+        let centre_distance2 = |tile: &Tile| {
+            let x = (tile.x + tile.width / 2) as f64 - dimensions.x as f64 / 2.;
+            let y = (tile.y + tile.height / 2) as f64 - dimensions.y as f64 / 2.;
+            x*x + y*y
+        };
+        tiles.sort_by(|a, b| centre_distance2(b).total_cmp(&centre_distance2(a)));
+        tiles
+    }
+
+    /// the number of points across and down the tile,
+    /// where each point colours a block of quality x quality pixels
+    fn points(&self, quality: usize) -> (usize, usize) {
+        (self.width.div_ceil(quality), self.height.div_ceil(quality))
+    }
+}
+
+/// which pixels of the image a render fills in
+pub(crate) enum RenderPass {
+    /// every pixel
+    Full,
+    /// the points between the samples already rendered at twice the quality
+    Refine,
+    /// only the given areas, the rest of the image being rendered already
+    Exposed(Vec<Tile>)
+}
+
+/// the rows of a view symmetric about the real axis, 
+/// where the rows on one side are reflections of the other
+struct Mirror {
+    /// twice the row the real axis is on 
+    /// (row y is the reflection of row axis2 - y)
+    axis2: usize,
+    /// the larger side, which gets rendered
+    rows: Range<usize>
+}
+impl Mirror {
+    /// None if the view doesn't cross the real axis, or the axis
+    /// is between rows so the sides don't line up
+    fn new(dimensions: &ScreenDimensions, center: &ComplexType, pixel_step: FloatExp) -> Option<Mirror> {
+        // how many pixels the center is above the axis
+        let offset = match center {
+            ComplexType::Double(c) => c.im / pixel_step.to_f64(),
+            ComplexType::DoubleDouble(c) => (c.im / DoubleDouble::from(pixel_step.to_f64())).to_f64(),
+            ComplexType::Big(c) => (&c.im / pixel_step.to_fbig()).to_f64().value()
+        };
+        let axis2 = dimensions.y as f64 - 2. * offset;
+        if (axis2 - axis2.round()).abs() > MIRROR_TOLERANCE || axis2.round() < 0. || axis2.round() > 2. * (dimensions.y - 1) as f64 {
+            return None;
+        }
+
+        let axis2 = axis2.round() as usize;
+        // starting on a block of the coarsest quality,
+        // so each pass's points line up with the last one's
+        let rows = match axis2 < dimensions.y {
+            true => axis2.div_ceil(2) / MAX_QUALITY * MAX_QUALITY..dimensions.y,
+            false => 0..axis2 / 2 + 1
+        };
+        Some(Mirror { axis2, rows })
+    }
+
+    /// the row which is a reflection of the given rendered row,
+    /// if it's in the image and not rendered itself
+    fn reflected_row(&self, y: usize, height: usize) -> Option<usize> {
+        let reflected = self.axis2.checked_sub(y)?;
+        match reflected < height && !self.rows.contains(&reflected) {
+            true => Some(reflected),
+            false => None
+        }
+    }
 }
 
 struct Renderer {
     fractal: Fractal,
     dimensions: ScreenDimensions,
-    start_y: usize,
-    thread_height: usize,
     center: ComplexType,
-    pixel_step: f64,
+    pixel_step: FloatExp,
     max_iterations: u32,
     bailout2: f64,
     image: Arc<Mutex<Image>>,
+    /// the output of each pixel of the image, when it's kept for recolouring
+    outputs: Option<Arc<Mutex<PointOutputs>>>,
     layers: Layers,
     quality: usize,
     thread_cancel: Arc<AtomicBool>,
     can_cancel: bool,
-    reference_orbit: Arc<Option<ReferenceOrbit>>,
-    progress_tracker: Arc<Mutex<usize>>
+    reference_orbit: Option<Arc<ReferenceOrbit>>,
+    /// the center's offset from the reference orbit
+    reference_offset: ComplexExp,
+    progress_tracker: Arc<AtomicUsize>,
+    /// whether to fill regions surrounded by the same output without iterating them
+    solid_guessing: bool,
+    /// the rows to reflect, when the view is symmetric
+    mirror: Option<Mirror>,
+    /// whether the image already has the samples at twice the quality,
+    /// so only the points between them are rendered
+    refine: bool
 }
 impl Renderer {
-    fn render_image(self) { 
-        let split = ThreadSplitter {
-            x_excess: self.dimensions.x % self.quality,
-            y_excess: self.thread_height % self.quality,
-            x_end: self.dimensions.x / self.quality,
-            y_end: self.thread_height / self.quality
-        };
+    /// renders tiles taken from the shared queue until there are none left,
+    /// so threads which finish early take work from the others
+    fn render_tiles(&self, tiles: &Mutex<Vec<Tile>>) {
+        loop {
+            if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
+                return;
+            }
 
-        match self.center {
-            ComplexType::Double(_) => self.render_double(&split),
-            ComplexType::Big(_) => match self.fractal {
-                Fractal::Mandelbrot => self.render_arbitrary_perturbed(&split),
-                _ => self.render_arbitrary(&split)
+            let tile = tiles.lock().unwrap().pop();
+            match tile {
+                Some(tile) => self.render_tile(&tile),
+                None => return
             }
         }
     }
 
-    fn render_double(&self, split: &ThreadSplitter) {
-        for x in 0..=split.x_end {
-            for y in 0..=split.y_end {
-                let z = ComplexType::Double(Complex::new(
-                    (self.center.real_f64() - self.dimensions.x as f64/2.0 * self.pixel_step) + x as f64 * self.pixel_step * self.quality as f64, 
-                    (self.center.im_f64() - self.dimensions.y as f64/2.0 * self.pixel_step) + (self.start_y+y*self.quality) as f64 * self.pixel_step,
-                ));
-                self.set_pixels(z, x, y, split);
+    /// renders the tile's outputs into its own buffer, then colours them into the image
+    /// (so the image is only locked once per tile)
+    fn render_tile(&self, tile: &Tile) {
+        let mut pixels = PointOutputs::new(tile.width * tile.height, &self.layers);
+        // the points already rendered are kept, shrinking to their new block size
+        if let (true, Some(outputs)) = (self.refine, &self.outputs) {
+            let outputs = outputs.lock().unwrap();
+            for y in 0..tile.height {
+                pixels.copy_from(y * tile.width, &outputs, (tile.y + y) * self.dimensions.x + tile.x, tile.width);
+            }
+        }
+        match self.center {
+            ComplexType::Double(_) => self.render_double(tile, &mut pixels),
+            ComplexType::DoubleDouble(c) => self.render_double_double(c, tile, &mut pixels),
+            ComplexType::Big(_) => match self.fractal.can_perturb_at(self.pixel_step) {
+                true => self.render_arbitrary_perturbed(tile, &mut pixels),
+                false => self.render_arbitrary(tile, &mut pixels)
+            }
+        }
 
-                if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
-                    return;
-                }
+        // a cancelled tile is unfinished
+        if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
+            return;
+        }
+
+        // coloured before locking the image, so other threads aren't kept waiting
+        let colours: Vec<Color> = (0..pixels.len()).map(|i| self.layers.colour_point(&pixels, i)).collect();
+        // each row of the tile, and the row it's reflected to
+        let rows: Vec<(usize, usize)> = (0..tile.height).flat_map(|y| {
+            let reflected = self.mirror.as_ref().and_then(|m| m.reflected_row(tile.y + y, self.dimensions.y));
+            [Some(tile.y + y), reflected].into_iter().flatten().map(move |row| (y, row))
+        }).collect();
+
+        let mut im = self.image.lock().unwrap();
+        for &(y, row) in rows.iter() {
+            for x in 0..tile.width {
+                im.set_pixel((tile.x + x) as u32, row as u32, colours[y * tile.width + x]);
             }
         }
+        drop(im);
+        if let Some(outputs) = &self.outputs {
+            let mut outputs = outputs.lock().unwrap();
+            for &(y, row) in rows.iter() {
+                outputs.copy_from(row * self.dimensions.x + tile.x, &pixels, y * tile.width, tile.width);
+            }
+        }
+        self.progress_tracker.fetch_add(rows.len() * tile.width, Ordering::Relaxed);
+    }
+
+    fn render_double(&self, tile: &Tile, pixels: &mut PointOutputs) {
+        let pixel_step = self.pixel_step.to_f64();
+        let left = self.center.real_f64() - self.dimensions.x as f64/2.0 * pixel_step;
+        let top = self.center.im_f64() - self.dimensions.y as f64/2.0 * pixel_step;
+
+        self.render_points(tile, pixels, |x, y| {
+            let z = ComplexType::Double(Complex::new(
+                left + (tile.x+x*self.quality) as f64 * pixel_step, 
+                top + (tile.y+y*self.quality) as f64 * pixel_step,
+            ));
+            (self.layers.point_output(&self.fractal, z, self.max_iterations, self.bailout2), false)
+        });
     }
     
-    fn render_arbitrary(&self, split: &ThreadSplitter) {
+    fn render_double_double(&self, center: ComplexDD, tile: &Tile, pixels: &mut PointOutputs) {
+        let pixel_step = self.pixel_step.to_f64();
+        let half_dims = ComplexDD::from_complex(Complex::new(
+            (self.dimensions.x as f64 / 2. - tile.x as f64) * pixel_step,
+            (self.dimensions.y as f64 / 2. - tile.y as f64) * pixel_step
+        ));
+        let topleft = center - half_dims;
+
+        self.render_points(tile, pixels, |x, y| {
+            let offset = ComplexDD::from_complex(Complex::new(
+                (x * self.quality) as f64, 
+                (y * self.quality) as f64
+            )) * pixel_step;
+            let z = ComplexType::DoubleDouble(topleft + offset);
+            (self.layers.point_output(&self.fractal, z, self.max_iterations, self.bailout2), false)
+        });
+    }
+
+    fn render_arbitrary(&self, tile: &Tile, pixels: &mut PointOutputs) {
         let center = match self.center {
             ComplexType::Big(ref c) => c.clone(),
-            ComplexType::Double(_) => panic!("center needs to be made arbitrary for arbitrary precision")
+            ComplexType::Double(_) | ComplexType::DoubleDouble(_) => panic!("center needs to be made arbitrary for arbitrary precision")
         };
         let dims = BigComplex::from_f64s(self.dimensions.x as f64, self.dimensions.y as f64);
-        // not sure what precision this should be yet - might need to change dynamically
-        let pixel_step = FBig::try_from(self.pixel_step).unwrap().with_precision(100).value();
+        let precision = precision_for(self.pixel_step);
+        let pixel_step = self.pixel_step.to_fbig().with_precision(precision).value();
 
-        let quality = FBig::try_from(self.quality).unwrap().with_precision(100).value();
+        let topleft = center - (dims/2.0) * &pixel_step;
 
-        let mut x_add = FBig::ZERO;
-        let start_y = FBig::try_from(self.start_y).unwrap() * &pixel_step;
-        let mut y_add;
+        self.render_points(tile, pixels, |x, y| {
+            let z = ComplexType::Big(BigComplex::new(
+                &topleft.real + FBig::from(tile.x + x*self.quality) * &pixel_step, 
+                &topleft.im + FBig::from(tile.y + y*self.quality) * &pixel_step,
+            ));
+            (self.layers.point_output(&self.fractal, z, self.max_iterations, self.bailout2), false)
+        });
+    }
 
-        let topleft = center - (dims/2.0) * &pixel_step;
+    fn render_arbitrary_perturbed(&self, tile: &Tile, pixels: &mut PointOutputs) {
+        let reference_orbit = match &self.reference_orbit {
+            Some(orbit) => orbit,
+            None => panic!("perturbation needs a reference orbit")
+        };
 
-        for x in 0..=split.x_end {
-            y_add = FBig::ZERO;
-            for y in 0..=split.y_end {
-                let z = ComplexType::Big(BigComplex::new(
-                    &topleft.real + &x_add * &pixel_step, 
-                    &topleft.im + &start_y + &y_add * &pixel_step,
-                ));
-                y_add += &quality;
-                self.set_pixels(z, x, y, split);
+        let half_width = (self.dimensions.x as f64 / 2.) * self.pixel_step;
+        let half_height = (self.dimensions.y as f64 / 2.) * self.pixel_step;
+        let dc = |x: usize, y: usize| self.reference_offset + ComplexExp::new(
+            -half_width + (tile.x + x * self.quality) as f64 * self.pixel_step,
+            -half_height + (tile.y + y * self.quality) as f64 * self.pixel_step
+        );
+        
+        let glitched = self.render_points(tile, pixels, |x, y| {
+            self.layers.point_output_perturbed(
+                &self.fractal, dc(x, y), &reference_orbit.ref_z, reference_orbit.max_ref_iteration, 
+                reference_orbit.bla.as_ref(), self.max_iterations, self.bailout2
+            )
+        });
+
+        if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
+            return;
+        }
+        self.fix_glitches(glitched.into_iter().map(|(x, y)| (x, y, dc(x, y))).collect(), tile, pixels);
+    }
+
+    /// finds the output of every point in the tile
+    /// 
+    /// with solid guessing, points inside rectangles with the same output
+    /// all around their border are filled in without being iterated
+    /// 
+    /// when refining, the points from the last pass (every other point
+    /// in both directions) are left as they are
+    /// 
+    /// # Returns
+    /// the points which glitched
+    fn render_points(&self, tile: &Tile, pixels: &mut PointOutputs, point: impl Fn(usize, usize) -> (PointOutput, bool)) -> Vec<(usize, usize)> {
+        let (x_points, y_points) = tile.points(self.quality);
+        let mut outputs = vec![None; x_points * y_points];
 
+        if self.solid_guessing {
+            self.guess_rectangle((0, 0), (x_points-1, y_points-1), x_points, &mut outputs, &point);
+        }
+
+        let mut glitched = Vec::new();
+        for y in 0..y_points {
+            for x in 0..x_points {
                 if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
-                    return;
+                    return glitched;
                 }
+
+                if self.refine && x % 2 == 0 && y % 2 == 0 {
+                    continue;
+                }
+
+                let (output, point_glitched) = outputs[y * x_points + x].take().unwrap_or_else(|| point(x, y));
+                if point_glitched {
+                    glitched.push((x, y));
+                }
+                self.fill_pixels(&output, x, y, tile, pixels);
             }
-            x_add += &quality;
         }
+
+        glitched
     }
 
-    fn render_arbitrary_perturbed(&self, split: &ThreadSplitter) {
-        let reference_orbit = match self.reference_orbit.as_ref() {
-            Some(orbit) => orbit,
-            None => panic!("perturbation needs a reference orbit")
-        };
+    /// Mariani-Silver subdivision: fills the rectangle between the given corners (inclusive)
+    /// if its border is all the same, otherwise splits it into 4 and tries again
+    fn guess_rectangle(
+        &self, (x0, y0): (usize, usize), (x1, y1): (usize, usize), x_points: usize,
+        outputs: &mut [Option<(PointOutput, bool)>], point: &impl Fn(usize, usize) -> (PointOutput, bool)
+    ) {
+        let border = (x0..=x1).flat_map(|x| [(x, y0), (x, y1)])
+            .chain((y0+1..y1).flat_map(|y| [(x0, y), (x1, y)]));
 
-        let half_width = (self.dimensions.x as f64 / 2.) * &self.pixel_step;
-        let half_height = (self.dimensions.y as f64 / 2.) * &self.pixel_step;
+        let mut solid = true;
+        for (x, y) in border {
+            if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
+                return;
+            }
 
-        let start_y = self.start_y as f64 * &self.pixel_step;
-        
-        for y in 0..=split.y_end {
-            for x in 0..=split.x_end {
-                let dc = Complex::new(
-                    -&half_width + (x * &self.quality) as f64 * &self.pixel_step,
-                    -&half_height + &start_y + (y * &self.quality) as f64 * &self.pixel_step
-                );
-                self.set_pixels_perturbation(dc, &reference_orbit.ref_z, reference_orbit.max_ref_iteration, x, y, split);
+            let i = y * x_points + x;
+            if outputs[i].is_none() {
+                outputs[i] = Some(point(x, y));
+            }
+            // glitched points aren't reliable enough to guess from
+            solid &= outputs[i].as_ref().is_some_and(|(output, glitched)| {
+                !glitched && outputs[y0 * x_points + x0].as_ref().is_some_and(|(first, _)| first == output)
+            });
+        }
 
-                if self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel {
-                    return;
+        // every point is on the border
+        if x1 - x0 < 2 || y1 - y0 < 2 { return }
+
+        if solid {
+            let (output, _) = outputs[y0 * x_points + x0].clone().unwrap();
+            for y in y0+1..y1 {
+                for x in x0+1..x1 {
+                    outputs[y * x_points + x] = Some((output.clone(), false));
                 }
             }
+            return;
         }
+
+        let (mid_x, mid_y) = ((x0 + x1) / 2, (y0 + y1) / 2);
+        self.guess_rectangle((x0, y0), (mid_x, mid_y), x_points, outputs, point);
+        self.guess_rectangle((mid_x, y0), (x1, mid_y), x_points, outputs, point);
+        self.guess_rectangle((x0, mid_y), (mid_x, y1), x_points, outputs, point);
+        self.guess_rectangle((mid_x, mid_y), (x1, y1), x_points, outputs, point);
     }
 
-    fn set_pixels(&self, z: ComplexType, x: usize, y: usize, split: &ThreadSplitter) {
-        let colour: Color = self.layers.colour_pixel(&self.fractal, z, self.max_iterations,  self.bailout2);
-            
-        let mut im = self.image.lock().unwrap();
+    /// re-renders glitched pixels from secondary reference orbits centred inside them,
+    /// until none are left or too many reference orbits have been made
+    /// 
+    /// pixels which are still glitched keep their current output
+    fn fix_glitches(&self, mut glitched: Vec<(usize, usize, ComplexExp)>, tile: &Tile, pixels: &mut PointOutputs) {
+        let center = match &self.reference_orbit {
+            Some(orbit) => &orbit.center,
+            None => panic!("glitches only come from perturbation")
+        };
 
-        let width = if x == split.x_end {split.x_excess} else {self.quality};
-        let height = if y == split.y_end {split.y_excess} else {self.quality};
-        
-        for i in 0..width {
-            for j in 0..height {
-                im.set_pixel(
-                    (x*self.quality+i) as u32, 
-                    (self.start_y + y*self.quality + j) as u32, 
-                    colour
-                );
+        for _ in 0..MAX_SECONDARY_REFERENCES {
+            if glitched.is_empty() || (self.thread_cancel.load(Ordering::Relaxed) && self.can_cancel) {
+                return;
             }
+
+            let (_, _, reference_dc) = glitched[glitched.len() / 2];
+            let max_dc = glitched.iter()
+                .map(|(_, _, dc)| (*dc - reference_dc).abs_squared())
+                .fold(FloatExp::ZERO, |max, dc2| if dc2 > max { dc2 } else { max }).sqrt();
+            let reference_orbit = ReferenceOrbit::new(
+                &self.fractal,
+                &(center + &BigComplex::from_complex_exp(reference_dc)),
+                self.max_iterations as usize,
+                self.bailout2,
+                max_dc,
+                precision_for(self.pixel_step)
+            );
+
+            glitched.retain(|(x, y, dc)| {
+                let (output, still_glitched) = self.layers.point_output_perturbed(
+                    &self.fractal, *dc - reference_dc, &reference_orbit.ref_z, reference_orbit.max_ref_iteration, 
+                    reference_orbit.bla.as_ref(), self.max_iterations, self.bailout2
+                );
+                self.fill_pixels(&output, *x, *y, tile, pixels);
+                still_glitched
+            });
         }
+    }
 
-        *self.progress_tracker.lock().unwrap() += width*height;
+    /// sets the block of the tile's pixels the given point represents at the current quality
+    fn fill_pixels(&self, output: &PointOutput, x: usize, y: usize, tile: &Tile, pixels: &mut PointOutputs) {
+        // blocks are cut off at the edge of the tile
+        let width = self.quality.min(tile.width - x*self.quality);
+        let height = self.quality.min(tile.height - y*self.quality);
+        
+        for j in 0..height {
+            let row = (y*self.quality + j) * tile.width + x*self.quality;
+            pixels.fill(row, width, output);
+        }
     }
+}
 
-    fn set_pixels_perturbation(&self, dc: Complex, ref_z: &Vec<Complex>, max_ref_iteration: usize, x: usize, y: usize, split: &ThreadSplitter) {
-        let colour: Color = self.layers.colour_pixel_implementors_perturbed(
-            &self.fractal, dc, ref_z, max_ref_iteration, self.max_iterations, self.bailout2
-        );
-            
-        let mut im = self.image.lock().unwrap();
+/// start of a saved reference orbit file, with the version of its layout
+const ORBIT_FILE_HEADER: &[u8] = b"MREF1";
 
-        let width = if x == split.x_end {split.x_excess} else {self.quality};
-        let height = if y == split.y_end {split.y_excess} else {self.quality};
-        
-        for i in 0..width {
-            for j in 0..height {
-                im.set_pixel(
-                    (x*self.quality+i) as u32, 
-                    (self.start_y + y*self.quality + j) as u32, 
-                    colour
-                );
+/// the path a reference orbit is saved to, next to the save file it's for
+/// (None if it isn't a save file)
+fn get_orbit_path(save_path: &std::path::Path) -> Option<std::path::PathBuf> {
+    let name = save_path.file_name()?.to_str()?.strip_suffix("-save.txt")?;
+    Some(save_path.with_file_name(format!["{}-orbit.bin", name]))
+}
+
+fn push_u64(bytes: &mut Vec<u8>, x: u64) {
+    bytes.extend_from_slice(&x.to_le_bytes());
+}
+
+fn push_f64(bytes: &mut Vec<u8>, x: f64) {
+    bytes.extend_from_slice(&x.to_le_bytes());
+}
+
+fn push_string(bytes: &mut Vec<u8>, string: &str) {
+    push_u64(bytes, string.len() as u64);
+    bytes.extend_from_slice(string.as_bytes());
+}
+
+/// written in base 10, with enough digits to keep its precision
+/// (unlimited precision numbers are written with at least the digits of a double)
+fn push_big(bytes: &mut Vec<u8>, x: &FBig) {
+    let digits = x.precision().max(64);
+    push_string(bytes, &x.clone().with_base_and_precision::<10>(digits).value().to_string());
+}
+
+/// removes the first n bytes, returning None if there aren't enough
+fn take_bytes<'a>(bytes: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+    if bytes.len() < n { return None }
+    let (taken, rest) = bytes.split_at(n);
+    *bytes = rest;
+    Some(taken)
+}
+
+fn take_u64(bytes: &mut &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(take_bytes(bytes, 8)?.try_into().ok()?))
+}
+
+fn take_f64(bytes: &mut &[u8]) -> Option<f64> {
+    Some(f64::from_le_bytes(take_bytes(bytes, 8)?.try_into().ok()?))
+}
+
+fn take_string(bytes: &mut &[u8]) -> Option<String> {
+    let len = take_u64(bytes)? as usize;
+    String::from_utf8(take_bytes(bytes, len)?.to_vec()).ok()
+}
+
+fn take_big(bytes: &mut &[u8], precision: usize) -> Option<FBig> {
+    Some(parse_decimal(&take_string(bytes)?)?.with_precision(precision).value())
+}
+
+/// bits of arbitrary precision needed to tell apart pixels of the given step
+fn precision_for(pixel_step: FloatExp) -> usize {
+    (-pixel_step.ln() / LN_2).ceil().max(0.) as usize + PRECISION_GUARD_BITS
+}
+
+struct ReferenceOrbit {
+    /// the reference orbit, starting from 0 + 0i
+    /// (or the center for julia sets)
+    ref_z: Vec<Complex>,
+    /// the iteration just before the referencre orbit diverged
+    max_ref_iteration: usize,
+    /// approximations for skipping iterations along the orbit
+    /// (only for the mandelbrot and julia sets)
+    bla: Option<BlaTable>,
+    /// the point the orbit is from
+    center: BigComplex,
+    /// export string of the fractal the orbit is for
+    fractal: String,
+    bailout2: f64,
+    /// the furthest a pixel can be from the center
+    /// for the approximations to be valid
+    max_dc: FloatExp,
+    /// the furthest a pixel can be from the center
+    /// for the bla table to be valid
+    bla_dc: FloatExp,
+    /// bits the orbit is calculated with
+    precision: usize,
+    /// the next z and its memory, for extending the orbit
+    z: BigComplex,
+    memory: OrbitMemory<BigComplex>,
+    /// whether the orbit escaped, so it can't be extended
+    escaped: bool
+}
+impl ReferenceOrbit {
+    /// `max_dc` is the furthest a pixel will be from the center
+    /// 
+    /// `precision` is the bits the orbit is calculated with
+    fn new(fractal: &Fractal, center: &BigComplex, max_iterations: usize, bailout2: f64, max_dc: FloatExp, precision: usize) -> ReferenceOrbit {
+        let center = center.with_precision(precision);
+        // julia sets start from the pixel, everything else from the critical point
+        let z = match fractal.is_julia() {
+            true => center.clone(),
+            false => BigComplex::from_f64s(0., 0.)
+        };
+
+        let mut orbit = ReferenceOrbit {
+            ref_z: Vec::with_capacity(max_iterations),
+            max_ref_iteration: 0,
+            bla: None,
+            center,
+            fractal: fractal.get_export_string(),
+            bailout2,
+            max_dc,
+            bla_dc: max_dc,
+            precision,
+            z,
+            memory: OrbitMemory::new_big(),
+            escaped: false
+        };
+        orbit.iterate(fractal, max_iterations);
+        orbit.bla = orbit.make_bla(fractal);
+        orbit
+    }
+
+    /// iterates the orbit until it escapes or reaches the max iterations
+    fn iterate(&mut self, fractal: &Fractal, max_iterations: usize) {
+        for i in self.ref_z.len()..max_iterations {
+            self.ref_z.push(self.z.to_complex());
+            if self.z.abs_squared() < self.bailout2 {
+                fractal.iterate_big(&mut self.z, &self.center, &mut self.memory);
+                self.max_ref_iteration = i;
+            } else {
+                self.escaped = true;
+                break;
             }
         }
+    }
+
+    fn make_bla(&self, fractal: &Fractal) -> Option<BlaTable> {
+        match fractal {
+            Fractal::Mandelbrot => Some(BlaTable::new(&self.ref_z, self.max_ref_iteration, Complex::new(1., 0.), self.bla_dc.to_f64())),
+            Fractal::Julia(_) => Some(BlaTable::new(&self.ref_z, self.max_ref_iteration, Complex::new(0., 0.), 0.)),
+            _ => None
+        }
+    }
+
+    /// whether the bla table can be used for pixels up to `reach` from the center
+    /// without being too cautious for them
+    fn bla_fits(&self, reach: FloatExp) -> bool {
+        self.bla.is_none() || (reach <= self.bla_dc && reach * BLA_SHRINK_LIMIT >= self.bla_dc)
+    }
+
+    /// the orbit continued up to the new max iterations,
+    /// without recalculating the iterations it already has,
+    /// with its bla table remade for pixels up to `bla_dc` from the center
+    fn extended(&self, fractal: &Fractal, max_iterations: usize, bla_dc: FloatExp) -> ReferenceOrbit {
+        let mut orbit = ReferenceOrbit {
+            ref_z: self.ref_z.clone(),
+            max_ref_iteration: self.max_ref_iteration,
+            bla: None,
+            center: self.center.clone(),
+            fractal: self.fractal.clone(),
+            bailout2: self.bailout2,
+            max_dc: self.max_dc,
+            bla_dc,
+            precision: self.precision,
+            z: self.z.clone(),
+            memory: self.memory.clone(),
+            escaped: self.escaped
+        };
+        orbit.iterate(fractal, max_iterations);
+        orbit.bla = orbit.make_bla(fractal);
+        orbit
+    }
+
+    /// writes the orbit to a binary file, 
+    /// so it doesn't have to be recalculated when the location is loaded
+    fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let mut bytes = ORBIT_FILE_HEADER.to_vec();
+        push_string(&mut bytes, &self.fractal);
+        push_f64(&mut bytes, self.bailout2);
+        push_string(&mut bytes, &self.max_dc.to_string());
+        push_u64(&mut bytes, self.precision as u64);
+        push_u64(&mut bytes, self.max_ref_iteration as u64);
+        push_u64(&mut bytes, self.escaped as u64);
+        push_u64(&mut bytes, self.memory.iteration as u64);
+        for x in [&self.center.real, &self.center.im, &self.z.real, &self.z.im, 
+                  &self.memory.previous_z.real, &self.memory.previous_z.im] {
+            push_big(&mut bytes, x);
+        }
+        push_u64(&mut bytes, self.ref_z.len() as u64);
+        for z in self.ref_z.iter() {
+            push_f64(&mut bytes, z.real);
+            push_f64(&mut bytes, z.im);
+        }
 
-        *self.progress_tracker.lock().unwrap() += width*height;
+        fs::write(path, bytes)
+    }
+
+    /// reads an orbit written by save,
+    /// returning None if the file is missing or invalid
+    fn load(path: &std::path::Path, fractal: &Fractal) -> Option<ReferenceOrbit> {
+        let file = fs::read(path).ok()?;
+        let mut bytes = file.strip_prefix(ORBIT_FILE_HEADER)?;
+        let bytes = &mut bytes;
+
+        let fractal_string = take_string(bytes)?;
+        if fractal_string != fractal.get_export_string() { return None }
+        let bailout2 = take_f64(bytes)?;
+        let max_dc = FloatExp::parse(&take_string(bytes)?).ok()?;
+        let precision = take_u64(bytes)? as usize;
+        let max_ref_iteration = take_u64(bytes)? as usize;
+        let escaped = take_u64(bytes)? != 0;
+        let iteration = take_u64(bytes)? as u32;
+        let center = BigComplex::new(take_big(bytes, precision)?, take_big(bytes, precision)?);
+        let z = BigComplex::new(take_big(bytes, precision)?, take_big(bytes, precision)?);
+        let previous_z = BigComplex::new(take_big(bytes, precision)?, take_big(bytes, precision)?);
+
+        let len = take_u64(bytes)? as usize;
+        if bytes.len() != len.checked_mul(16)? { return None }
+        let mut ref_z = Vec::with_capacity(len);
+        for _ in 0..len {
+            ref_z.push(Complex::new(take_f64(bytes)?, take_f64(bytes)?));
+        }
+
+        let mut orbit = ReferenceOrbit {
+            ref_z, max_ref_iteration, center, bailout2, max_dc, precision, z, escaped,
+            bla: None,
+            bla_dc: max_dc,
+            fractal: fractal_string,
+            memory: OrbitMemory { previous_z, iteration }
+        };
+        orbit.bla = orbit.make_bla(fractal);
+        Some(orbit)
     }
-}
 
-struct ReferenceOrbit {
-    /// the reference orbit, starting from 0 + 0i
-    ref_z: Vec<Complex>,
-    /// the iteration just before the referencre orbit diverged
-    max_ref_iteration: usize
-}
-impl ReferenceOrbit {
-    fn new(fractal: &Fractal, center: &BigComplex, max_iterations: usize, bailout2: f64) -> ReferenceOrbit {
-        let mut ref_z: Vec<Complex> = Vec::with_capacity(max_iterations);
-        let mut max_ref_iteration= 0;
-
-        let mut z = BigComplex::from_f64s(0., 0.);
-        for i in 0..max_iterations {
-            ref_z.push(z.to_complex());
-            if z.abs_squared() < bailout2 {
-                fractal.iterate_big(&mut z, center);
-                max_ref_iteration = i;
-            } else {
-                break;
-            }
+    /// returns the center's offset from the orbit if the orbit can be used
+    /// to render around it, with pixels up to max_dc from the center
+    /// 
+    /// needs the same fractal and bailout, enough precision
+    /// and every pixel within the distance the orbit was made for
+    fn offset_for(
+        &self, fractal: &Fractal, center: &BigComplex, pixel_step: FloatExp, max_dc: FloatExp, bailout2: f64
+    ) -> Option<ComplexExp> {
+        if self.fractal != fractal.get_export_string() || self.bailout2 != bailout2 ||
+            self.precision + PRECISION_GUARD_BITS / 2 < precision_for(pixel_step) {
+            return None;
+        }
+
+        // measured in pixels so it fits in a double
+        let step = pixel_step.to_fbig();
+        let pixels = Complex::new(
+            ((&center.real - &self.center.real) / &step).to_f64().value(),
+            ((&center.im - &self.center.im) / &step).to_f64().value()
+        );
+        let distance = pixels.abs_squared().sqrt();
+        match distance.is_finite() && distance * pixel_step + max_dc <= self.max_dc {
+            true => Some(ComplexExp::from_complex(pixels) * pixel_step),
+            false => None
         }
-    
-        ReferenceOrbit { ref_z, max_ref_iteration }
     }
 }
 
@@ -1231,8 +2873,10 @@ struct Exporter {
     name: String,
     dims: ScreenDimensions,
     image: Arc<Mutex<Image>>,
-    progress_tracker: Arc<Mutex<usize>>,
-    visualiser_params: VisualiserParams
+    progress_tracker: Arc<AtomicUsize>,
+    visualiser_params: VisualiserParams,
+    /// the orbit the image was perturbed from, saved with it
+    reference_orbit: Option<Arc<ReferenceOrbit>>
 }
 impl Exporter {
     fn new() -> Exporter {
@@ -1250,8 +2894,9 @@ impl Exporter {
             dims: ScreenDimensions::from_tuple((0, 0)),
             images_path: images_path,
             image: Arc::new(Mutex::new(Image::empty())),
-            progress_tracker: Arc::new(Mutex::new(0)),
-            visualiser_params: VisualiserParams::empty()
+            progress_tracker: Arc::new(AtomicUsize::new(0)),
+            visualiser_params: VisualiserParams::empty(),
+            reference_orbit: None
         }
     }
 
@@ -1284,9 +2929,9 @@ impl Exporter {
         name: &String, 
         dimensions: ScreenDimensions,
         current_dimensions: &ScreenDimensions,
-        visualiser_pixel_step: f64,
+        visualiser_pixel_step: FloatExp,
         visualiser_params: VisualiserParams
-    ) -> f64 {
+    ) -> FloatExp {
         self.name = name.clone();
         self.dims = dimensions;
         self.visualiser_params = visualiser_params;
@@ -1306,7 +2951,7 @@ impl Exporter {
             Image::gen_image_color(self.dims.x as u16, self.dims.y as u16, 
                                    Color::new(0.0, 0.0, 0.0, 1.0)
         )));
-        self.progress_tracker = Arc::new(Mutex::new(0));
+        self.progress_tracker = Arc::new(AtomicUsize::new(0));
         self.exporting = true;
 
         pixel_step
@@ -1344,6 +2989,13 @@ impl Exporter {
         save_path.push(format!["{}-save.txt", name]);
         self.visualiser_params.save_params(&save_path);
 
+        if let (Some(orbit), Some(orbit_path)) = (&self.reference_orbit, get_orbit_path(&save_path)) {
+            // the orbit only saves recalculating it, so the export still succeeds without it
+            if let Err(error) = orbit.save(&orbit_path) {
+                println!("unable to save reference orbit: {}", error);
+            }
+        }
+
         self.exporting = false;
     }
 }
@@ -1352,7 +3004,7 @@ impl Exporter {
 pub struct VideoTimestamp {
     fractal: Fractal,
     center: ComplexType,
-    pixel_step: f64,
+    pixel_step: FloatExp,
     max_iterations: f32,
     bailout2: f64,
     layers: Layers,
@@ -1372,14 +3024,15 @@ impl VideoTimestamp {
     }
 
     fn from_params(params: VisualiserParams, percent: f32) -> VideoTimestamp {
+        let pixel_step = 0.005 / FloatExp::parse(&params.magnification).unwrap();
         let mut center = ComplexType::Double(Complex::new(0. ,0.));
-        center.update_real_from_string(params.center_re);
-        center.update_im_from_string(params.center_im);
+        center.update_real_from_string(params.center_re, precision_for(pixel_step));
+        center.update_im_from_string(params.center_im, precision_for(pixel_step));
 
         VideoTimestamp { 
             fractal: params.fractal,
             center, 
-            pixel_step: 0.005 / params.magnification.parse::<f64>().unwrap(), 
+            pixel_step, 
             max_iterations: params.max_iterations.parse::<f32>().unwrap(), 
             bailout2: params.bailout2.parse::<f64>().unwrap(), 
             layers: params.layers, 
@@ -1390,7 +3043,7 @@ impl VideoTimestamp {
     fn lerp_timestamps(timestamp1: &VideoTimestamp, timestamp2: &VideoTimestamp, percent: f32) -> VideoTimestamp {
         let percent = ((percent - timestamp1.percent) / (timestamp2.percent - timestamp1.percent)) as f64;
 
-        let pixel_step = lerpf64_ln(timestamp1.pixel_step, timestamp2.pixel_step, percent);
+        let pixel_step = lerp_floatexp_ln(timestamp1.pixel_step, timestamp2.pixel_step, percent);
 
         // TODO: fix interpolating complex
         // let arb_precision = pixel_step <= ARB_PRECISION_THRESHOLD;
@@ -1450,7 +3103,7 @@ pub struct VideoRecorder {
     /// converts the timestamp's pixel step for the given dimensions
     // pixel_step_multiplier: f64,
     image: Arc<Mutex<Image>>,
-    progress_tracker: Arc<Mutex<usize>>
+    progress_tracker: Arc<AtomicUsize>
 }
 impl VideoRecorder {
     fn new() -> VideoRecorder {
@@ -1476,7 +3129,7 @@ impl VideoRecorder {
             dims: ScreenDimensions::from_tuple((0, 0)), 
             // pixel_step_multiplier: 0.0,
             image: Arc::new(Mutex::new(Image::empty())),
-            progress_tracker: Arc::new(Mutex::new(0))
+            progress_tracker: Arc::new(AtomicUsize::new(0))
         }
     }
 
@@ -1597,7 +3250,7 @@ impl VideoRecorder {
 
     fn get_progress(&self) -> f32 {
         let pixels = self.dims.numpixels();
-        let this_frame = self.progress_tracker.lock().unwrap().clone();
+        let this_frame = self.progress_tracker.load(Ordering::Relaxed);
 
         (pixels * self.completed_frames + this_frame) as f32 / ( pixels * self.frames ) as f32
     }
@@ -1634,7 +3287,7 @@ impl VideoRecorder {
 
         self.frames = time * fps;
         self.completed_frames = 0;
-        self.progress_tracker = Arc::new(Mutex::new(0));
+        self.progress_tracker = Arc::new(AtomicUsize::new(0));
         self.dims = dimensions.clone();
         self.changed = false;
         
@@ -1720,7 +3373,7 @@ impl VideoRecorder {
         ];
         self.image.lock().unwrap().export_png(path);
 
-        self.progress_tracker = Arc::new(Mutex::new(0));
+        self.progress_tracker = Arc::new(AtomicUsize::new(0));
     }
 
     fn finish_recording(&mut self) {
@@ -1786,7 +3439,7 @@ pub struct Visualiser {
     fractal: Fractal,
     current_dimensions: ScreenDimensions,
     center: ComplexType,
-    pixel_step: f64,
+    pixel_step: FloatExp,
     max_iterations: f32,
     /// the squared distance of the bailout
     bailout2: f64,
@@ -1796,18 +3449,32 @@ pub struct Visualiser {
     thread_cancel: Arc<AtomicBool>,
     layers: Layers,
     image: Arc<Mutex<Image>>,
+    /// the layer outputs of each pixel of the image,
+    /// so it can be recoloured without iterating again
+    outputs: Arc<Mutex<PointOutputs>>,
     texture: Texture2D, 
     /// the percentage increase in zoom per second
-    move_speed: f64,
+    move_speed: FloatExp,
     /// how many pixels each complex number generated represents
     pub quality: usize,
     /// quality before decreasing quality when user stopped moving
     saved_quality: usize,
+    /// the quality of the render in progress
+    render_quality: usize,
+    /// the quality the current view has been fully rendered at,
+    /// which the next refinement pass builds on
+    rendered_quality: Option<usize>,
+    /// the center and pixel step the image was last rendered at
+    image_view: Option<(ComplexType, FloatExp)>,
     arb_precision: bool,
     moving: bool,
     exporter: Exporter,
     video_recorder: VideoRecorder,
-    progress_tracker: Arc<Mutex<usize>>,
+    progress_tracker: Arc<AtomicUsize>,
+    /// the last reference orbit made, kept to be reused
+    reference_orbit: Option<Arc<ReferenceOrbit>>,
+    /// whether regions surrounded by the same output are filled without iterating
+    solid_guessing: bool,
     render_start_time: Instant,
     last_render_time: f32
 }
@@ -1820,7 +3487,9 @@ impl Visualiser {
         view_dimensions: (usize, usize),
         layers: Layers
     ) -> Visualiser {
-        Visualiser { fractal, pixel_step, max_iterations, layers,
+        let outputs = PointOutputs::new(view_dimensions.0 * view_dimensions.1, &layers);
+        Visualiser { fractal, max_iterations, layers,
+            pixel_step: FloatExp::from(pixel_step),
             bailout2: bailout.powi(2),
             current_dimensions: ScreenDimensions::from_tuple(view_dimensions),
             center: ComplexType::Double(Complex::new(-0.5, 0.0)),
@@ -1828,18 +3497,24 @@ impl Visualiser {
                 Image::gen_image_color(view_dimensions.0 as u16, view_dimensions.1 as u16, 
                                        Color::new(0.0, 0.0, 0.0, 1.0)
             ))),
+            outputs: Arc::new(Mutex::new(outputs)),
             texture: Texture2D::empty(),
-            move_speed: START_ZOOM_SPEED, 
+            move_speed: FloatExp::from(START_ZOOM_SPEED), 
             thread_pool: ThreadPool::new((num_cpus::get_physical()-1).max(1)),
             rendering: false,
             thread_cancel: Arc::new(AtomicBool::new(false)),
             quality: 2,
             saved_quality: 1,
+            render_quality: 2,
+            rendered_quality: None,
+            image_view: None,
             arb_precision: false,
             moving: false,
             exporter: Exporter::new(),
             video_recorder: VideoRecorder::new(),
-            progress_tracker: Arc::new(Mutex::new(0)),
+            progress_tracker: Arc::new(AtomicUsize::new(0)),
+            reference_orbit: None,
+            solid_guessing: false,
             render_start_time: Instant::now(),
             last_render_time: f32::INFINITY
         }
@@ -1847,22 +3522,23 @@ impl Visualiser {
 
     pub fn set_fractal(&mut self, new_fractal: Fractal) {
         self.fractal = new_fractal;
+        self.layers.match_fractal(&self.fractal);
         self.quality = MAX_QUALITY;
-        self.set_pixel_step(0.005);
+        self.set_pixel_step(FloatExp::from(0.005));
         self.generate_image();
     }
 
-    pub fn get_magnification(&self) -> f64 {
+    pub fn get_magnification(&self) -> FloatExp {
         0.005 / self.pixel_step
     }
 
-    pub fn load(&mut self, pixel_step: f64, center_x: f64, center_y: f64, max_iterations: f32) {
+    pub fn load(&mut self, pixel_step: FloatExp, center_x: f64, center_y: f64, max_iterations: f32) {
         self.set_pixel_step(pixel_step);
         self.center = ComplexType::Double(Complex::new(center_x, center_y));
         self.max_iterations = max_iterations;
     }
 
-    pub fn load_big(&mut self, pixel_step: f64, center_x: &str, center_y: &str, max_iterations: f32) {
+    pub fn load_big(&mut self, pixel_step: FloatExp, center_x: &str, center_y: &str, max_iterations: f32) {
         self.set_pixel_step(pixel_step);
         self.center = ComplexType::Big(BigComplex::from_string_base10(center_x, center_y));
         self.update_precision();
@@ -1886,9 +3562,9 @@ impl Visualiser {
         // todo: remove all unwrapping so even less chance of invalid data crashing
 
         self.fractal = params.fractal;
-        self.center.update_real_from_string(params.center_re);
-        self.center.update_im_from_string(params.center_im);
-        self.set_pixel_step(0.005 / params.magnification.parse::<f64>().unwrap());
+        self.set_pixel_step(0.005 / FloatExp::parse(&params.magnification).unwrap());
+        self.center.update_real_from_string(params.center_re, precision_for(self.pixel_step));
+        self.center.update_im_from_string(params.center_im, precision_for(self.pixel_step));
         self.max_iterations = params.max_iterations.parse::<f32>().unwrap();
         self.bailout2 = params.bailout2.parse::<f64>().unwrap();
         self.layers = params.layers;
@@ -1906,6 +3582,11 @@ impl Visualiser {
         let params = VisualiserParams::import_from_str(&params_string);
         self.load_params(params);
 
+        // reused by the render if it's still valid for the loaded view
+        if let Some(orbit) = get_orbit_path(file_path).and_then(|path| ReferenceOrbit::load(&path, &self.fractal)) {
+            self.reference_orbit = Some(Arc::new(orbit));
+        }
+
         self.update_precision();
         self.generate_image();
     }
@@ -1918,6 +3599,8 @@ impl Visualiser {
             Image::gen_image_color(self.current_dimensions.x as u16, self.current_dimensions.y as u16, 
                                    Color::new(0.0, 0.0, 0.0, 1.0)
         )));
+        self.outputs = Arc::new(Mutex::new(PointOutputs::new(self.current_dimensions.numpixels(), &self.layers)));
+        self.image_view = None;
         
         self.generate_image();
     }
@@ -1927,22 +3610,203 @@ impl Visualiser {
         self.thread_pool.join();
         self.thread_cancel.store(false, Ordering::Relaxed);
         self.rendering = false;
+        // the image is part way between renders
+        self.rendered_quality = None;
     }  
 
     fn generate_image(&mut self) {
         if self.rendering {
             if self.moving || self.video_recorder.previewing { return }
-            self.quality += 1;
+            self.quality = (self.quality * 2).min(MAX_QUALITY);
             self.cancel_current_render();
         }
+        self.rendered_quality = None;
+
+        self.reproject_image(&self.center.clone());
+        self.start_render(self.center.clone(), self.quality, RenderPass::Full);
+    }
+
+    /// moves the image to follow the view after only panning, so just the
+    /// strips which have come into view need rendering
+    /// 
+    /// the view is moved by whole pixels, so the image lines up
+    fn pan_image(&mut self) {
+        // panning while rendering is caught up with once the render finishes
+        if self.rendering { return }
+        let (image_center, image_pixel_step, image_quality) = match (&self.image_view, self.rendered_quality) {
+            (Some((center, pixel_step)), Some(quality)) if *pixel_step == self.pixel_step => (center.clone(), *pixel_step, quality),
+            _ => return self.generate_image()
+        };
+
+        // moved by whole pixels, so the image lines up
+        let (dx, dy) = Visualiser::pixel_offset(&image_center, &self.center, image_pixel_step);
+        let (dx, dy) = (dx.round(), dy.round());
+        let offset_by = |dx: f64, dy: f64| {
+            Visualiser::offset_center(&image_center, ComplexExp::new(dx * image_pixel_step, dy * image_pixel_step))
+        };
+        // and by whole blocks, so the points kept stay where the next refinement expects them
+        let block = image_quality as f64;
+        let (block_dx, block_dy) = ((dx / block).round() * block, (dy / block).round() * block);
+        if !self.moving {
+            // the view stays where it was stopped (to the nearest pixel)
+            self.center = offset_by(dx, dy);
+            // and between blocks, the image can't be kept
+            if block_dx != dx || block_dy != dy {
+                return self.generate_image();
+            }
+        }
+        if block_dx == 0. && block_dy == 0. { return }
+        if block_dx.abs() >= self.current_dimensions.x as f64 || block_dy.abs() >= self.current_dimensions.y as f64 {
+            return self.generate_image();
+        }
+        // the rest of a pan in progress is caught up with once it reaches another block
+        let center = offset_by(block_dx, block_dy);
+
+        // never finer than the rest of the image, so it can all be refined together
+        let quality = self.quality.max(image_quality);
+        self.reproject_image(&center);
+        self.start_render(center, quality, RenderPass::Exposed(
+            Visualiser::exposed_areas(&self.current_dimensions, block_dx as isize, block_dy as isize, quality)
+        ));
+    }
+
+    /// the areas of the image which weren't in view before moving by the given pixels,
+    /// grown to whole blocks of pixels at the given quality
+    fn exposed_areas(dimensions: &ScreenDimensions, dx: isize, dy: isize, quality: usize) -> Vec<Tile> {
+        // the rows and columns of the new image still inside the old one
+        let kept = |moved: isize, size: usize| {
+            let start = match moved < 0 {
+                true => moved.unsigned_abs().next_multiple_of(quality),
+                false => 0
+            };
+            let end = match moved > 0 {
+                true => (size - moved as usize) / quality * quality,
+                false => size
+            };
+            start..end.max(start)
+        };
+        let (kept_x, kept_y) = (kept(dx, dimensions.x), kept(dy, dimensions.y));
+        let strips = [
+            Tile { x: 0, y: 0, width: dimensions.x, height: kept_y.start },
+            Tile { x: 0, y: kept_y.end, width: dimensions.x, height: dimensions.y - kept_y.end },
+            Tile { x: 0, y: kept_y.start, width: kept_x.start, height: kept_y.len() },
+            Tile { x: kept_x.end, y: kept_y.start, width: dimensions.x - kept_x.end, height: kept_y.len() }
+        ];
+
+        strips.into_iter().filter(|strip| strip.width > 0 && strip.height > 0).collect()
+    }
+
+    /// shifts and scales the image from the view it was rendered at to the given center
+    /// at the current pixel step, as a placeholder until it's rendered again
+    fn reproject_image(&mut self, center: &ComplexType) {
+        let (image_center, image_pixel_step) = match &self.image_view {
+            Some((image_center, pixel_step)) if image_center != center || *pixel_step != self.pixel_step => (image_center.clone(), *pixel_step),
+            _ => return
+        };
+        let mut image = self.image.lock().unwrap();
+        let mut outputs = self.outputs.lock().unwrap();
+        let (width, height) = (image.width(), image.height());
+        if width != self.current_dimensions.x || height != self.current_dimensions.y { return }
+
+        let (dx, dy) = Visualiser::pixel_offset(&image_center, center, image_pixel_step);
+        let scale = (self.pixel_step / image_pixel_step).to_f64();
+        // moved out rather than cloned, with the new ones starting black and unrendered
+        let old = std::mem::replace(&mut image.bytes, [0, 0, 0, 255].repeat(width * height));
+        let old_outputs = std::mem::replace(&mut *outputs, PointOutputs::new(width * height, &self.layers));
+        // outputs from before the layers changed can't be used
+        let keep_outputs = old_outputs.fits(&self.layers);
+        for y in 0..height {
+            let old_y = (height as f64 / 2. + dy + (y as f64 - height as f64 / 2.) * scale).round();
+            for x in 0..width {
+                let old_x = (width as f64 / 2. + dx + (x as f64 - width as f64 / 2.) * scale).round();
+                let i = y * width + x;
+                // parts of the view which weren't in the image are left black
+                if old_x >= 0. && old_x < width as f64 && old_y >= 0. && old_y < height as f64 {
+                    let old_i = old_y as usize * width + old_x as usize;
+                    image.bytes[4*i..4*i+4].copy_from_slice(&old[4*old_i..4*old_i+4]);
+                    if keep_outputs {
+                        outputs.copy_from(i, &old_outputs, old_i, 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// colours the image again from the outputs it was rendered with,
+    /// for when only the colouring of the layers has changed
+    /// 
+    /// a render in progress has the old colouring, so is started again
+    fn recolour_image(&mut self) {
+        if self.rendering {
+            return self.generate_image();
+        }
+        self.layers.generate_palettes(self.max_iterations);
+        self.colour_outputs();
+
+        Texture2D::delete(&self.texture);
+        self.texture = Texture2D::from_image(&self.image.lock().unwrap());
+    }
+
+    /// passes every pixel's output through the layers into the image
+    fn colour_outputs(&self) {
+        let outputs = self.outputs.lock().unwrap();
+        let colours: Vec<Color> = (0..outputs.len()).map(|i| self.layers.colour_point(&outputs, i)).collect();
+        drop(outputs);
+
+        let mut image = self.image.lock().unwrap();
+        let width = image.width();
+        for (i, colour) in colours.into_iter().enumerate() {
+            image.set_pixel((i % width) as u32, (i / width) as u32, colour);
+        }
+    }
+
+    /// how many pixels (at the pixel step) to gets from one center to another
+    fn pixel_offset(from: &ComplexType, to: &ComplexType, pixel_step: FloatExp) -> (f64, f64) {
+        let pixel_step = pixel_step.to_fbig();
+        (
+            ((to.real_fbig() - from.real_fbig()) / &pixel_step).to_f64().value(),
+            ((to.im_fbig() - from.im_fbig()) / &pixel_step).to_f64().value()
+        )
+    }
+
+    /// the center moved by the given offset, in the same precision
+    fn offset_center(center: &ComplexType, offset: ComplexExp) -> ComplexType {
+        match center {
+            ComplexType::Double(c) => ComplexType::Double(*c + offset.to_complex()),
+            ComplexType::DoubleDouble(c) => ComplexType::DoubleDouble(*c + ComplexDD::from_complex(offset.to_complex())),
+            ComplexType::Big(c) => ComplexType::Big(c + &BigComplex::from_complex_exp(offset))
+        }
+    }
+
+    /// renders the next progressive pass of the current view, 
+    /// filling in the points between the ones already rendered
+    /// (or starts again if there's nothing to build on)
+    fn refine_image(&mut self) {
+        match self.rendered_quality {
+            Some(quality) if quality > 1 => self.start_render(self.center.clone(), quality / 2, RenderPass::Refine),
+            Some(_) => {},
+            None => self.generate_image()
+        }
+    }
+
+    /// renders the image around the given center, 
+    /// which is only different to the view's while it's being panned
+    fn start_render(&mut self, center: ComplexType, quality: usize, pass: RenderPass) {
         self.rendering = true;
+        self.render_quality = quality;
+        self.image_view = Some((center.clone(), self.pixel_step));
 
         self.layers.generate_palettes(self.max_iterations);
+        let mut outputs = self.outputs.lock().unwrap();
+        if !outputs.fits(&self.layers) {
+            *outputs = PointOutputs::new(self.current_dimensions.numpixels(), &self.layers);
+        }
+        drop(outputs);
 
-        self.progress_tracker = Arc::new(Mutex::new(0));
+        self.progress_tracker = Arc::new(AtomicUsize::new(0));
         self.generate_given_image(
-            self.image.clone(), self.current_dimensions.clone(), None, self.pixel_step, None, 
-            self.quality, Arc::clone(&self.progress_tracker), true
+            self.image.clone(), Some(Arc::clone(&self.outputs)), self.current_dimensions.clone(), None, self.pixel_step, Some(center), 
+            quality, pass, Arc::clone(&self.progress_tracker), true
         );
         self.render_start_time = Instant::now();
         
@@ -1950,85 +3814,133 @@ impl Visualiser {
         self.texture = Texture2D::from_image(&self.image.lock().unwrap());
     }
 
-    fn get_needed_center(center: ComplexType, pixel_step: f64) -> ComplexType {
-        let arb_precision = pixel_step <= ARB_PRECISION_THRESHOLD;
+    /// returns the center in the precision needed to render at the pixel step
+    /// 
+    /// double-doubles are only used for fractals which can't be perturbed,
+    /// as perturbation only needs doubles for each pixel
+    /// 
+    /// fractional powers are only calculated in doubles, 
+    /// so more precision would be slower without being any sharper
+    fn get_needed_center(center: ComplexType, pixel_step: FloatExp, fractal: &Fractal) -> ComplexType {
+        if pixel_step > FloatExp::from(ARB_PRECISION_THRESHOLD) || fractal.has_fractional_power() {
+            center.make_double()
+        } else if pixel_step > FloatExp::from(DOUBLE_DOUBLE_THRESHOLD) && !fractal.can_perturb_at(pixel_step) {
+            center.make_double_double()
+        } else {
+            let precision = precision_for(pixel_step);
+            center.make_big(precision).with_precision(precision)
+        }
+    }
 
-        match &center {
-            ComplexType::Big(c) => if !arb_precision {
-                ComplexType::Double(c.to_complex())
-            } else {
-                center.clone()
-            },
-            ComplexType::Double(_) => center.clone()
+    /// returns a reference orbit to render around the center with,
+    /// and the center's offset from it
+    /// 
+    /// the last orbit made is reused if it can be (extending it to the max iterations
+    /// and remaking its bla table for the view), 
+    /// so panning and zooming in don't have to wait for a new one
+    fn get_reference_orbit(
+        &mut self, fractal: &Fractal, center: &BigComplex, pixel_step: FloatExp, dimensions: &ScreenDimensions
+    ) -> (Arc<ReferenceOrbit>, ComplexExp) {
+        let max_dc = (dimensions.x as f64).hypot(dimensions.y as f64) / 2. * pixel_step;
+        let max_iterations = self.max_iterations as usize;
+
+        if let Some(orbit) = &self.reference_orbit {
+            if let Some(offset) = orbit.offset_for(fractal, center, pixel_step, max_dc, self.bailout2) {
+                let offset_distance = offset.abs_squared().sqrt();
+                let bla_fits = orbit.bla_fits(offset_distance + max_dc);
+                if (orbit.ref_z.len() < max_iterations && !orbit.escaped) || !bla_fits {
+                    let bla_dc = match bla_fits {
+                        true => orbit.bla_dc,
+                        false => offset_distance + max_dc * REFERENCE_REACH
+                    };
+                    self.reference_orbit = Some(Arc::new(orbit.extended(fractal, max_iterations, bla_dc)));
+                }
+                return (Arc::clone(self.reference_orbit.as_ref().unwrap()), offset);
+            }
         }
+
+        let orbit = Arc::new(ReferenceOrbit::new(
+            fractal, center, max_iterations, self.bailout2, max_dc * REFERENCE_REACH, precision_for(pixel_step)
+        ));
+        self.reference_orbit = Some(Arc::clone(&orbit));
+        (orbit, ComplexExp::ZERO)
     }
 
     /// generates and stores the mandlebrot image
     /// for the current parameters
     /// 
     /// param is None, the visualiser's is used
-    pub fn generate_given_image(
+    pub(crate) fn generate_given_image(
         &mut self, 
         image: Arc<Mutex<Image>>, 
+        outputs: Option<Arc<Mutex<PointOutputs>>>,
         dimensions: ScreenDimensions, 
         fractal: Option<Fractal>,
-        pixel_step: f64,
+        pixel_step: FloatExp,
         center: Option<ComplexType>,
         quality: usize,
-        progress_tracker: Arc<Mutex<usize>>,
+        pass: RenderPass,
+        progress_tracker: Arc<AtomicUsize>,
         can_cancel: bool
     ) {
-        let arb_precision = pixel_step <= ARB_PRECISION_THRESHOLD;
-
+        let fractal = fractal.unwrap_or(self.fractal.clone());
         let center = Visualiser::get_needed_center(
             center.unwrap_or(self.center.clone()),
-            pixel_step
+            pixel_step,
+            &fractal
         );
-        let fractal = fractal.unwrap_or(self.fractal.clone());
-
-        let mut center = center;
-        let reference_orbit = if !arb_precision {
-            Arc::new(None)
-        } else {
-            Arc::new(Some(ReferenceOrbit::new(
-                &self.fractal,
-                match &center {
-                    ComplexType::Double(_) => {
-                        center = center.make_big();
-                        match &center {
-                            ComplexType::Double(_) => panic!("failed to make center big"),
-                            ComplexType::Big(c) => c
-                        }
-                    },
-                    ComplexType::Big(c) => c
-                }, 
-                self.max_iterations as usize,
-                self.bailout2
-            )))
+        let (reference_orbit, reference_offset) = match (&center, fractal.can_perturb_at(pixel_step)) {
+            (ComplexType::Big(c), true) => {
+                let (orbit, offset) = self.get_reference_orbit(&fractal, c, pixel_step, &dimensions);
+                (Some(orbit), offset)
+            },
+            _ => (None, ComplexExp::ZERO)
         };
         
-        let thread_height = dimensions.y / THREADS;
-
-        for t in 0..THREADS {
-            let renderer = Renderer {
-                fractal: fractal.clone(),
-                dimensions: dimensions.clone(),
-                start_y: t * thread_height,
-                thread_height,
-                center: center.clone(),
-                pixel_step: pixel_step.clone(),
-                max_iterations: self.max_iterations.clone() as u32,
-                bailout2: self.bailout2.clone(),
-                image: Arc::clone(&image),
-                layers: self.layers.clone(),
-                quality,
-                thread_cancel: Arc::clone(&self.thread_cancel),
-                reference_orbit: Arc::clone(&reference_orbit),
-                progress_tracker: Arc::clone(&progress_tracker),
-                can_cancel
-            };
+        // exposed areas are too thin to be worth mirroring
+        let mirror = match fractal.is_symmetric() && self.layers.is_symmetric() && !matches!(pass, RenderPass::Exposed(_)) {
+            true => Mirror::new(&dimensions, &center, pixel_step),
+            false => None
+        };
+        let areas = match (&pass, &mirror) {
+            (RenderPass::Exposed(areas), _) => areas.clone(),
+            (_, Some(mirror)) => vec![Tile { x: 0, y: mirror.rows.start, width: dimensions.x, height: mirror.rows.len() }],
+            (_, None) => vec![Tile { x: 0, y: 0, width: dimensions.x, height: dimensions.y }]
+        };
+        // the rest of the image is already done
+        let area_pixels: usize = areas.iter().map(|area| area.width * area.height).sum();
+        if let RenderPass::Exposed(_) = pass {
+            progress_tracker.fetch_add(dimensions.numpixels() - area_pixels, Ordering::Relaxed);
+        }
+        let tiles = Arc::new(Mutex::new(Tile::make_tiles(&dimensions, &areas, quality)));
+        let solid_guessing = self.solid_guessing && fractal.can_guess() && self.layers.can_guess();
+        let renderer = Arc::new(Renderer {
+            fractal,
+            dimensions,
+            center,
+            pixel_step,
+            max_iterations: self.max_iterations.clone() as u32,
+            bailout2: self.bailout2.clone(),
+            image,
+            outputs,
+            layers: self.layers.clone(),
+            quality,
+            thread_cancel: Arc::clone(&self.thread_cancel),
+            reference_orbit,
+            reference_offset,
+            progress_tracker,
+            can_cancel,
+            solid_guessing,
+            mirror,
+            refine: matches!(pass, RenderPass::Refine)
+        });
+
+        // every thread takes tiles from the same queue
+        for _ in 0..self.thread_pool.max_count() {
+            let renderer = Arc::clone(&renderer);
+            let tiles = Arc::clone(&tiles);
             self.thread_pool.execute(move || {
-                renderer.render_image()
+                renderer.render_tiles(&tiles)
             });
         }
     }
@@ -2076,11 +3988,13 @@ impl Visualiser {
             self.load_timestamp(&timestamp);
             self.generate_given_image(
                 Arc::clone(&self.video_recorder.image), 
+                None,
                 self.video_recorder.dims.clone(), 
                 None,
                 timestamp.pixel_step, 
                 None,
                 1,
+                RenderPass::Full,
                 Arc::clone(&self.video_recorder.progress_tracker),
                 true
             );
@@ -2096,13 +4010,15 @@ impl Visualiser {
 
         let mut center = center.clone();
 
-        center.real += self.move_speed * dt * match (is_key_down(KeyCode::A), is_key_down(KeyCode::D)) {
+        let movement = (self.move_speed * dt).to_f64();
+
+        center.real += movement * match (is_key_down(KeyCode::A), is_key_down(KeyCode::D)) {
             (true, false) => -1.0,
             (false, true) => 1.0,
             _ => {moved_y = false; 0.0}
         };
 
-        center.im += self.move_speed * dt * match (is_key_down(KeyCode::W), is_key_down(KeyCode::S)) {
+        center.im += movement * match (is_key_down(KeyCode::W), is_key_down(KeyCode::S)) {
             (true, false) => -1.0,
             (false, true) => 1.0,
             _ => {moved_x = false; 0.0}
@@ -2116,7 +4032,7 @@ impl Visualiser {
 
         let mut center = center.clone();
 
-        let movement = FBig::try_from(self.move_speed * dt).unwrap().with_precision(0).value();
+        let movement = (self.move_speed * dt).to_fbig().with_precision(0).value();
 
         let old_real = center.real.clone();
         center.real += movement.clone() * FBig::try_from(match (is_key_down(KeyCode::A), is_key_down(KeyCode::D)) {
@@ -2151,6 +4067,9 @@ impl Visualiser {
             ComplexType::Double(c) => {
                 self.move_view_double(&c, dt)
             },
+            ComplexType::DoubleDouble(c) => {
+                self.move_view_big(&c.to_big(), dt)
+            },
             ComplexType::Big(ref c) => {
                 self.move_view_big(c, dt)
             }
@@ -2232,8 +4151,19 @@ impl Visualiser {
     fn user_teleport(&mut self) -> bool {
         if !is_key_pressed(KeyCode::T) {return false}
 
-        self.pixel_step = 0.005;
-        self.move_speed = 1.;
+        self.pixel_step = FloatExp::from(0.005);
+        self.move_speed = FloatExp::from(1.);
+
+        true
+    }
+
+    /// lets the user turn solid guessing on and off
+    /// 
+    /// returns if it has been changed
+    fn user_toggle_guessing(&mut self) -> bool {
+        if !is_key_pressed(KeyCode::G) {return false}
+
+        self.solid_guessing = !self.solid_guessing;
 
         true
     }
@@ -2252,14 +4182,22 @@ impl Visualiser {
 
         self.generate_given_image(
             Arc::clone(&self.exporter.image), 
+            None,
             self.exporter.dims.clone(), 
             None,
             pixel_step,
             None,
             1,
+            RenderPass::Full,
             Arc::clone(&self.exporter.progress_tracker),
             true
         );
+        // the export was only perturbed if it needed arbitrary precision
+        self.exporter.reference_orbit = match self.fractal.can_perturb_at(pixel_step) && 
+            pixel_step <= FloatExp::from(ARB_PRECISION_THRESHOLD) {
+            true => self.reference_orbit.clone(),
+            false => None
+        };
     }
 
     fn start_recording(&mut self, name: &String, dimensions: ScreenDimensions, time: usize, fps: usize) {
@@ -2282,9 +4220,10 @@ impl Visualiser {
 
     fn finish_render(&mut self) {
         self.rendering = false;
+        self.rendered_quality = Some(self.render_quality);
         self.last_render_time = self.render_start_time.elapsed().as_secs_f32();
         if self.last_render_time <= 1. / (MIN_FPS + FPS_DROP_EXCESS) as f32 && self.quality > 1 {
-            self.quality -= 1;
+            self.quality /= 2;
         }
     }
 
@@ -2316,7 +4255,7 @@ impl Visualiser {
         if self.render_start_time.elapsed().as_secs_f32() < 1. / MIN_FPS as f32 { return }
 
         if (self.moving || self.video_recorder.previewing) && self.quality < MAX_QUALITY {
-            self.quality += 1;
+            self.quality *= 2;
             self.cancel_current_render();
         }
     }
@@ -2333,37 +4272,51 @@ impl Visualiser {
         let zoomed = self.user_zoom(dt);
         let iter = self.user_change_max_iteration(dt);
         let tp = self.user_teleport();
+        let guessing = self.user_toggle_guessing();
 
         if is_key_pressed(KeyCode::Z) {
             println!("{} {} = {}x zoom\n{:?}\nreal: {} im: {}", 
                 self.max_iterations, self.pixel_step, 0.005/self.pixel_step, self.center, self.center.real_string(), self.center.im_string());
         }
     
-        if moved_view || zoomed || iter || tp {
+        if moved_view && !(zoomed || iter || tp || guessing) {
+            self.moving = true;
+            self.pan_image();
+        } else if moved_view || zoomed || iter || tp || guessing {
             self.moving = true;
             self.generate_image();
-        // user stopped moving - increase quality
-        } else if !self.rendering && self.quality > 1 {
+        // user stopped moving - catch up with panning done while rendering
+        } else if !self.rendering && self.image_view.as_ref().is_some_and(|(center, pixel_step)| {
+            *center != self.center || *pixel_step != self.pixel_step
+        }) {
             self.moving = false;
-            self.quality /= 2;
-            self.generate_image();
+            self.pan_image();
+        // then sharpen the image
+        } else if !self.rendering && self.rendered_quality != Some(1) {
+            self.moving = false;
+            self.refine_image();
         } else {
             self.moving = false;
         }
     }
 
+    /// keeps the center precise enough for the pixel step
+    /// (never lowering it, so precise centers aren't lost by zooming out)
     fn update_precision(&mut self) {
-        if self.pixel_step <= ARB_PRECISION_THRESHOLD && !self.arb_precision {
+        if self.pixel_step <= FloatExp::from(ARB_PRECISION_THRESHOLD) && !self.arb_precision {
             self.arb_precision = true;
-            self.center = self.center.make_big();
-            self.quality += 1 ;
-        } else if self.pixel_step > ARB_PRECISION_THRESHOLD && self.arb_precision {
+            self.quality = (self.quality * 2).min(MAX_QUALITY);
+        } else if self.pixel_step > FloatExp::from(ARB_PRECISION_THRESHOLD) && self.arb_precision {
             self.arb_precision = false;
         }
+
+        if self.arb_precision {
+            self.center = self.center.make_big(precision_for(self.pixel_step));
+        }
     }
 
-    fn set_pixel_step(&mut self, new: f64) {
-        if new <= 0.0 { return }
+    fn set_pixel_step(&mut self, new: FloatExp) {
+        if new <= FloatExp::ZERO { return }
         self.pixel_step = new;
         self.move_speed = new * (START_ZOOM_SPEED / 0.005);
         self.update_precision();
@@ -2372,7 +4325,7 @@ impl Visualiser {
     /// automatically zooms into the centre
     /// `speed` = percentage increase in zoom per second
     /// `target_pixel_step` = stops zooming once it reaches a certain zoom
-    pub fn play(&mut self, speed: f64, target_pixel_step: Option<f64>) {
+    pub fn play(&mut self, speed: f64, target_pixel_step: Option<FloatExp>) {
         match target_pixel_step {
             Some(t) => {
                 if self.pixel_step <= t {
@@ -2548,6 +4501,22 @@ impl Buhddabrot {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use layers::{LayerType, LayerRange};
+    use palettes::Palette;
+
+    /// a visualiser with a layer which colours everything outside the set
+    /// (the default layer has no strength, so its renders are all black)
+    fn visible_visualiser(view_dimensions: (usize, usize)) -> Visualiser {
+        let layers = Layers::new(vec![Layer::new(LayerType::Colour, LayerRange::OutSet, 1., Palette::default())], true);
+        let mut visualiser = Visualiser::new(Fractal::Mandelbrot, 0.005, 500., 4.5, view_dimensions, layers);
+        visualiser.layers.generate_palettes(visualiser.max_iterations);
+        visualiser
+    }
+
+    /// the colour after being stored in an image
+    fn as_pixel(colour: Color) -> Color {
+        Image::gen_image_color(1, 1, colour).get_pixel(0, 0)
+    }
 
     #[test]
     fn converges() {
@@ -2582,4 +4551,590 @@ mod tests {
         assert!(recorder.get_timestamp_at_percent(0.).is_some());
         assert!(recorder.get_timestamp_at_percent(1.).is_some());
     }
+
+    #[test]
+    fn perturbation_matches_iteration() {
+        let c = Complex::new(-1.75, -0.03);
+        let dc = Complex::new(1e-7, -2e-7);
+
+        for fractal in [
+            Fractal::BurningShip, Fractal::Celtic, Fractal::Buffalo, Fractal::Perpendicular, Fractal::Tricorn,
+            Fractal::Phoenix { p: JuliaSeed::new(-0.5, 0.1) }, Fractal::Hybrid(Hybrid::default())
+        ] {
+            let mut z = Complex::new(0., 0.);
+            let mut z_pixel = Complex::new(0., 0.);
+            let mut dz = Complex::new(0., 0.);
+            let (mut memory, mut memory_pixel, mut memory_dz) = 
+                (OrbitMemory::new_double(), OrbitMemory::new_double(), OrbitMemory::new_double());
+            for _ in 0..10 {
+                dz = fractal.iterate_perturbed(z, dz, dc, &mut memory_dz);
+                fractal.iterate_double(&mut z, c, &mut memory);
+                fractal.iterate_double(&mut z_pixel, c + dc, &mut memory_pixel);
+            }
+
+            assert!(((z + dz) - z_pixel).abs_squared() < 1e-20 * z_pixel.abs_squared().max(1.));
+        }
+    }
+
+    #[test]
+    fn julia_perturbation_matches_iteration() {
+        let center = Complex::new(0.1, -0.3);
+        let dz0 = Complex::new(1e-7, -2e-7);
+
+        for fractal in [
+            Fractal::Julia(JuliaSeed::new(-0.8, 0.156)), Fractal::TricornJulia(JuliaSeed::new(-0.8, 0.156))
+        ] {
+            let reference = ReferenceOrbit::new(&fractal, &BigComplex::from_complex(center), 11, BAILOUT, FloatExp::ZERO, 64);
+            let mut z_pixel = center + dz0;
+            let mut dz = dz0;
+            let mut memory = OrbitMemory::new_double();
+            for i in 0..10 {
+                dz = fractal.iterate_perturbed(reference.ref_z[i], dz, Complex::new(0., 0.), &mut memory);
+                fractal.iterate_double(&mut z_pixel, center + dz0, &mut OrbitMemory::new_double());
+            }
+
+            assert!(((reference.ref_z[10] + dz) - z_pixel).abs_squared() < 1e-20 * z_pixel.abs_squared().max(1.));
+        }
+    }
+
+    #[test]
+    fn perturbation_glitches() {
+        let layers = Layers::new(vec![Layer::default()], true);
+        let glitched = |fractal: &Fractal, dc: Complex| {
+            let reference = ReferenceOrbit::new(fractal, &BigComplex::from_f64s(-1., 0.), 50, BAILOUT, FloatExp::from(1.), 64);
+            layers.colour_pixel_implementors_perturbed(
+                fractal, ComplexExp::from_complex(dc), &reference.ref_z, reference.max_ref_iteration, None, 20, BAILOUT
+            ).1
+        };
+
+        // c = 0 lands on 0 while the reference is at -1, 
+        // which rebasing to the start of the reference fixes
+        assert!(!glitched(&Fractal::Mandelbrot, Complex::new(1., 0.)));
+        assert!(!glitched(&Fractal::Mandelbrot, Complex::new(1e-8, 0.)));
+        // hybrids can only rebase to where the reference is on the same formula,
+        // which is at -1 as well
+        assert!(glitched(&Fractal::Hybrid(Hybrid::default()), Complex::new(1., 0.)));
+    }
+
+    #[test]
+    fn extended_perturbation_matches_reference() {
+        let mut layers = Layers::new(vec![Layer::default()], true);
+        layers.generate_palettes(100.);
+        let dc = ComplexExp::new(FloatExp::parse("3e-400").unwrap(), FloatExp::parse("-1e-400").unwrap());
+
+        for (fractal, center) in [
+            (Fractal::Mandelbrot, Complex::new(0.3, 0.01)), 
+            (Fractal::Julia(JuliaSeed::new(-0.8, 0.156)), Complex::new(0.4, 0.2))
+        ] {
+            let reference = ReferenceOrbit::new(&fractal, &BigComplex::from_complex(center), 100, BAILOUT, FloatExp::ZERO, 64);
+            let colour = |dc: ComplexExp| layers.colour_pixel_implementors_perturbed(
+                &fractal, dc, &reference.ref_z, reference.max_ref_iteration, reference.bla.as_ref(), 100, BAILOUT
+            );
+
+            // far too close to the reference to escape any differently
+            assert_eq!(colour(dc), colour(ComplexExp::ZERO));
+            assert!(fractal.can_perturb_at(FloatExp::parse("1e-400").unwrap()));
+        }
+        assert!(!Fractal::BurningShip.can_perturb_at(FloatExp::parse("1e-400").unwrap()));
+    }
+
+    #[test]
+    fn reference_orbit_reuse() {
+        let fractal = Fractal::Mandelbrot;
+        let center = BigComplex::from_f64s(-0.1, 0.1);
+        let pixel_step = FloatExp::from(1e-5);
+        let orbit = ReferenceOrbit::new(&fractal, &center, 50, BAILOUT, FloatExp::from(1e-2), 64);
+
+        // panned by 10 pixels
+        let panned = BigComplex::from_f64s(-0.1 + 1e-4, 0.1);
+        let offset = orbit.offset_for(&fractal, &panned, pixel_step, FloatExp::from(1e-3), BAILOUT).unwrap();
+        assert!((offset.to_complex() - Complex::new(1e-4, 0.)).abs_squared() < 1e-24);
+        assert!(orbit.offset_for(&fractal, &panned, pixel_step, FloatExp::from(1e-2), BAILOUT).is_none());
+        assert!(orbit.offset_for(&Fractal::BurningShip, &panned, pixel_step, FloatExp::from(1e-3), BAILOUT).is_none());
+
+        let extended = orbit.extended(&fractal, 200, orbit.bla_dc);
+        let full = ReferenceOrbit::new(&fractal, &center, 200, BAILOUT, FloatExp::from(1e-2), 64);
+        assert_eq!(extended.ref_z, full.ref_z);
+        assert_eq!(extended.max_ref_iteration, full.max_ref_iteration);
+
+        // zoomed in far enough that the bla table is remade for the smaller view
+        assert!(orbit.bla_fits(FloatExp::from(5e-3)));
+        assert!(!orbit.bla_fits(FloatExp::from(1e-4)));
+        assert!(!orbit.bla_fits(FloatExp::from(2e-2)));
+        assert!(orbit.extended(&fractal, 50, FloatExp::from(1e-4)).bla_fits(FloatExp::from(1e-4)));
+    }
+
+    #[test]
+    fn reference_orbit_file_roundtrip() {
+        let fractal = Fractal::Mandelbrot;
+        let center = BigComplex::from_string_base10("-1.7490000000000000000001", "0.0000000000000000000003");
+        let orbit = ReferenceOrbit::new(&fractal, &center, 100, BAILOUT, FloatExp::parse("1e-20").unwrap(), 128);
+
+        let save_path = std::env::temp_dir().join("reference_orbit_test-save.txt");
+        let orbit_path = get_orbit_path(&save_path).unwrap();
+        assert!(orbit_path.ends_with("reference_orbit_test-orbit.bin"));
+        orbit.save(&orbit_path).unwrap();
+
+        let loaded = ReferenceOrbit::load(&orbit_path, &fractal).unwrap();
+        assert!(ReferenceOrbit::load(&orbit_path, &Fractal::BurningShip).is_none());
+
+        // a corrupted length too big to have that many bytes
+        let mut bytes = fs::read(&orbit_path).unwrap();
+        let len_start = bytes.len() - orbit.ref_z.len() * 16 - 8;
+        bytes[len_start..len_start+8].copy_from_slice(&(u64::MAX / 8).to_le_bytes());
+        fs::write(&orbit_path, bytes).unwrap();
+        assert!(ReferenceOrbit::load(&orbit_path, &fractal).is_none());
+        fs::remove_file(&orbit_path).unwrap();
+
+        assert_eq!(loaded.ref_z, orbit.ref_z);
+        assert_eq!((loaded.max_ref_iteration, loaded.escaped), (orbit.max_ref_iteration, orbit.escaped));
+        assert!((&loaded.center - &orbit.center).abs_squared() < 1e-70);
+        // carries on exactly where the saved orbit left off
+        assert_eq!(loaded.extended(&fractal, 200, loaded.bla_dc).ref_z, orbit.extended(&fractal, 200, orbit.bla_dc).ref_z);
+    }
+
+    #[test]
+    fn tiles_cover_image() {
+        let dimensions = ScreenDimensions::new(1001, 333);
+        let quality = 3;
+        let mut tiles = Tile::make_tiles(&dimensions, &[Tile { x: 0, y: 0, width: dimensions.x, height: dimensions.y }], quality);
+
+        let mut covered = vec![0; dimensions.x * dimensions.y];
+        for tile in tiles.iter() {
+            for x in tile.x..tile.x+tile.width {
+                for y in tile.y..tile.y+tile.height {
+                    covered[y * dimensions.x + x] += 1;
+                }
+            }
+        }
+        assert!(covered.iter().all(|n| *n == 1));
+
+        let first = tiles.pop().unwrap();
+        assert!((first.x..first.x+first.width).contains(&(dimensions.x / 2)));
+        assert!((first.y..first.y+first.height).contains(&(dimensions.y / 2)));
+        assert_eq!(first.points(quality), (TILE_POINTS, TILE_POINTS));
+    }
+
+    #[test]
+    fn tiles_merged_into_image() {
+        let mut visualiser = visible_visualiser((600, 600));
+        let dimensions = ScreenDimensions::new(150, 70);
+        let image = Arc::new(Mutex::new(Image::gen_image_color(150, 70, WHITE)));
+        let progress_tracker = Arc::new(AtomicUsize::new(0));
+
+        // away from the real axis, so every block is rendered rather than mirrored
+        let center = ComplexType::Double(Complex::new(-0.5, 0.8));
+        visualiser.generate_given_image(
+            Arc::clone(&image), None, dimensions.clone(), None, FloatExp::from(0.02), 
+            Some(center.clone()), 2, RenderPass::Full, Arc::clone(&progress_tracker), false
+        );
+        visualiser.thread_pool.join();
+
+        assert_eq!(progress_tracker.load(Ordering::Relaxed), dimensions.numpixels());
+        // each pixel has the colour of the point at the corner of its block
+        // (including the bottom right block, which is cut off by the image edge)
+        let image = image.lock().unwrap();
+        for y in 0..70 {
+            for x in 0..150 {
+                let (point_x, point_y) = (x / 2 * 2, y / 2 * 2);
+                let z = ComplexType::Double(Complex::new(-0.5 - 75. * 0.02 + point_x as f64 * 0.02, 0.8 - 70. * 0.01 + point_y as f64 * 0.02));
+                let colour = visualiser.layers.colour_pixel(&visualiser.fractal, z, visualiser.max_iterations as u32, visualiser.bailout2);
+                assert_eq!(image.get_pixel(x as u32, y as u32), as_pixel(colour));
+            }
+        }
+    }
+
+    #[test]
+    fn solid_guessing_matches() {
+        let mut visualiser = visible_visualiser((600, 600));
+        assert!(visualiser.fractal.can_guess() && visualiser.layers.can_guess());
+
+        let dimensions = ScreenDimensions::new(160, 96);
+        let mut render = |solid_guessing: bool| {
+            visualiser.solid_guessing = solid_guessing;
+            let image = Arc::new(Mutex::new(Image::gen_image_color(160, 96, WHITE)));
+            let outputs = Arc::new(Mutex::new(PointOutputs::new(dimensions.numpixels(), &visualiser.layers)));
+            let progress_tracker = Arc::new(AtomicUsize::new(0));
+            visualiser.generate_given_image(
+                Arc::clone(&image), Some(Arc::clone(&outputs)), dimensions.clone(), None, FloatExp::from(0.02), 
+                Some(ComplexType::Double(Complex::new(-0.5, 0.))), 1, RenderPass::Full, Arc::clone(&progress_tracker), false
+            );
+            visualiser.thread_pool.join();
+            // guessed points are counted as they're filled in
+            assert_eq!(progress_tracker.load(Ordering::Relaxed), dimensions.numpixels());
+            let image = image.lock().unwrap().clone();
+            let outputs = outputs.lock().unwrap().clone();
+            (image, outputs)
+        };
+
+        let (guessed, guessed_outputs) = render(true);
+        let (iterated, iterated_outputs) = render(false);
+        assert!(guessed.bytes == iterated.bytes && guessed_outputs == iterated_outputs);
+        assert!(!Fractal::BurningShip.can_guess());
+    }
+
+    #[test]
+    fn mirrored_rows_match() {
+        let mut visualiser = visible_visualiser((600, 600));
+        let dimensions = ScreenDimensions::new(120, 81);
+        let center = ComplexType::Double(Complex::new(-0.5, 0.2));
+
+        // the axis is between rows 30 and 31, so the rows below it are rendered
+        // (from the start of the block row 31 is in)
+        let mirror = Mirror::new(&dimensions, &center, FloatExp::from(0.02)).unwrap();
+        assert!(mirror.rows == (16..81) && mirror.reflected_row(50, 81) == Some(11) && mirror.reflected_row(31, 81).is_none());
+        assert!(Mirror::new(&dimensions, &ComplexType::Double(Complex::new(-0.5, 0.205)), FloatExp::from(0.02)).is_none());
+        assert!(Mirror::new(&dimensions, &ComplexType::Double(Complex::new(-0.5, 2.)), FloatExp::from(0.02)).is_none());
+
+        let mut render = |pass: RenderPass| {
+            let image = Arc::new(Mutex::new(Image::gen_image_color(120, 81, WHITE)));
+            let progress_tracker = Arc::new(AtomicUsize::new(0));
+            visualiser.generate_given_image(
+                Arc::clone(&image), None, dimensions.clone(), None, FloatExp::from(0.02), 
+                Some(center.clone()), 1, pass, Arc::clone(&progress_tracker), false
+            );
+            visualiser.thread_pool.join();
+            // mirrored rows are counted as they're copied
+            assert_eq!(progress_tracker.load(Ordering::Relaxed), dimensions.numpixels());
+            let image = image.lock().unwrap().clone();
+            image
+        };
+        // exposed areas aren't mirrored, so the whole image exposed is rendered point by point
+        let mirrored = render(RenderPass::Full);
+        let unmirrored = render(RenderPass::Exposed(vec![Tile { x: 0, y: 0, width: 120, height: 81 }]));
+        assert!(mirrored.bytes == unmirrored.bytes);
+
+        assert!(!Fractal::Julia(JuliaSeed::new(-0.12, 0.75)).is_symmetric());
+        assert!(Fractal::Julia(JuliaSeed::new(-1.2, 0.)).is_symmetric());
+    }
+
+    #[test]
+    fn refinement_matches_full_render() {
+        let mut visualiser = visible_visualiser((600, 600));
+        let dimensions = ScreenDimensions::new(150, 90);
+        let center = ComplexType::Double(Complex::new(-0.6, 0.13));
+
+        // refinement keeps the outputs of the last pass
+        let unrendered = PointOutputs::new(dimensions.numpixels(), &visualiser.layers);
+        let outputs = Arc::new(Mutex::new(unrendered.clone()));
+        let mut render = |image: &Arc<Mutex<Image>>, quality: usize, pass: RenderPass| {
+            let progress_tracker = Arc::new(AtomicUsize::new(0));
+            visualiser.generate_given_image(
+                Arc::clone(image), Some(Arc::clone(&outputs)), dimensions.clone(), None, FloatExp::from(0.02), 
+                Some(center.clone()), quality, pass, Arc::clone(&progress_tracker), false
+            );
+            visualiser.thread_pool.join();
+            assert_eq!(progress_tracker.load(Ordering::Relaxed), dimensions.numpixels());
+        };
+
+        let refined = Arc::new(Mutex::new(Image::gen_image_color(150, 90, WHITE)));
+        render(&refined, 4, RenderPass::Full);
+        let coarse = outputs.lock().unwrap().clone();
+        render(&refined, 2, RenderPass::Refine);
+        render(&refined, 1, RenderPass::Refine);
+        let refined_outputs = outputs.lock().unwrap().clone();
+        // the points of the first pass are kept rather than iterated again
+        // (the other side of the real axis is mirrored from them)
+        let rendered_rows = Mirror::new(&dimensions, &center, FloatExp::from(0.02)).unwrap().rows;
+        let first_pass_points = |outputs: &PointOutputs| {
+            let mut points = unrendered.clone();
+            for y in rendered_rows.clone().step_by(4) {
+                for x in (0..150).step_by(4) {
+                    points.copy_from(y * 150 + x, outputs, y * 150 + x, 1);
+                }
+            }
+            points
+        };
+        assert!(first_pass_points(&refined_outputs) == first_pass_points(&coarse));
+
+        let full = Arc::new(Mutex::new(Image::gen_image_color(150, 90, WHITE)));
+        render(&full, 1, RenderPass::Full);
+        assert!(refined.lock().unwrap().bytes == full.lock().unwrap().bytes && refined_outputs == *outputs.lock().unwrap());
+    }
+
+    #[test]
+    fn recolouring_matches_render() {
+        let mut visualiser = visible_visualiser((150, 90));
+        let dimensions = ScreenDimensions::new(150, 90);
+        let center = ComplexType::Double(Complex::new(-0.6, 0.13));
+
+        let render = |visualiser: &mut Visualiser, image: Arc<Mutex<Image>>| {
+            let outputs = Some(Arc::clone(&visualiser.outputs));
+            visualiser.generate_given_image(
+                image, outputs, dimensions.clone(), None, FloatExp::from(0.02), 
+                Some(center.clone()), 1, RenderPass::Full, Arc::new(AtomicUsize::new(0)), false
+            );
+            visualiser.thread_pool.join();
+        };
+        let image = Arc::clone(&visualiser.image);
+        render(&mut visualiser, image);
+
+        let recoloured_matches_render = |visualiser: &mut Visualiser| {
+            let old_image = visualiser.image.lock().unwrap().bytes.clone();
+            visualiser.layers.generate_palettes(visualiser.max_iterations);
+            visualiser.colour_outputs();
+            assert!(visualiser.image.lock().unwrap().bytes != old_image);
+
+            let full = Arc::new(Mutex::new(Image::gen_image_color(150, 90, WHITE)));
+            render(visualiser, Arc::clone(&full));
+            assert!(visualiser.image.lock().unwrap().bytes == full.lock().unwrap().bytes);
+        };
+        visualiser.layers.layers[0].palette.set_offset(0.4);
+        recoloured_matches_render(&mut visualiser);
+        assert!(visualiser.layers.layers[0].change_strength(0.5));
+        recoloured_matches_render(&mut visualiser);
+        visualiser.layers.layers[0].change_range(LayerRange::InSet);
+        assert!(matches!(visualiser.layers.layers[0].layer_range, LayerRange::InSet));
+        recoloured_matches_render(&mut visualiser);
+    }
+
+    #[test]
+    fn panning_renders_exposed_strips() {
+        let mut visualiser = visible_visualiser((150, 90));
+        let dimensions = ScreenDimensions::new(150, 90);
+        // powers of 2, so moving by whole pixels is exact
+        let pixel_step = FloatExp::from(1. / 64.);
+        // away from the real axis, so no rows are mirrored
+        let start = ComplexType::Double(Complex::new(-0.5, 1.));
+        let (dx, dy) = (7, -5);
+        let moved = Visualiser::offset_center(&start, ComplexExp::new(dx as f64 * pixel_step, dy as f64 * pixel_step));
+        assert!(Visualiser::pixel_offset(&start, &moved, pixel_step) == (7., -5.));
+
+        let areas = Visualiser::exposed_areas(&dimensions, dx, dy, 1);
+        assert!(areas == vec![
+            Tile { x: 0, y: 0, width: 150, height: 5 },
+            Tile { x: 143, y: 5, width: 7, height: 85 }
+        ]);
+        // grown to the blocks of 4 pixels the moved points are in
+        assert!(Visualiser::exposed_areas(&dimensions, dx, dy, 4) == vec![
+            Tile { x: 0, y: 0, width: 150, height: 8 },
+            Tile { x: 140, y: 8, width: 10, height: 82 }
+        ]);
+
+        let render = |visualiser: &mut Visualiser, image: &Arc<Mutex<Image>>, center: &ComplexType, quality: usize, pass: RenderPass| {
+            visualiser.generate_given_image(
+                Arc::clone(image), Some(Arc::clone(&visualiser.outputs)), dimensions.clone(), None, pixel_step, 
+                Some(center.clone()), quality, pass, Arc::new(AtomicUsize::new(0)), false
+            );
+            visualiser.thread_pool.join();
+        };
+        let pan = |visualiser: &mut Visualiser, image: &Arc<Mutex<Image>>, start: &ComplexType, moved: &ComplexType| {
+            visualiser.image = Arc::clone(image);
+            visualiser.image_view = Some((start.clone(), pixel_step));
+            visualiser.center = moved.clone();
+            visualiser.pixel_step = pixel_step;
+            visualiser.reproject_image(moved);
+        };
+        let full = Arc::new(Mutex::new(Image::gen_image_color(150, 90, WHITE)));
+        render(&mut visualiser, &full, &moved, 1, RenderPass::Full);
+
+        let panned = Arc::new(Mutex::new(Image::gen_image_color(150, 90, WHITE)));
+        render(&mut visualiser, &panned, &start, 1, RenderPass::Full);
+        pan(&mut visualiser, &panned, &start, &moved);
+        render(&mut visualiser, &panned, &moved, 1, RenderPass::Exposed(areas));
+        assert!(panned.lock().unwrap().bytes == full.lock().unwrap().bytes);
+
+        // strips rendered coarser than the image line up with its blocks, so it refines the same
+        let coarse = Arc::new(Mutex::new(Image::gen_image_color(150, 90, WHITE)));
+        render(&mut visualiser, &coarse, &start, 1, RenderPass::Full);
+        pan(&mut visualiser, &coarse, &start, &moved);
+        render(&mut visualiser, &coarse, &moved, 4, RenderPass::Exposed(Visualiser::exposed_areas(&dimensions, dx, dy, 4)));
+        render(&mut visualiser, &coarse, &moved, 2, RenderPass::Refine);
+        render(&mut visualiser, &coarse, &moved, 1, RenderPass::Refine);
+        let refined = visualiser.outputs.lock().unwrap().clone();
+        render(&mut visualiser, &full, &moved, 1, RenderPass::Full);
+        assert!(coarse.lock().unwrap().bytes == full.lock().unwrap().bytes && refined == *visualiser.outputs.lock().unwrap());
+    }
+
+    #[test]
+    fn double_double_matches_big() {
+        let c = BigComplex::from_string_base10("-1.7490000000000000000001", "0.0000000000000000000003");
+        let c = c.with_precision(200);
+
+        for fractal in [Fractal::BurningShip, Fractal::MagnetI, Fractal::Custom(Formula::default())] {
+            let mut z = fractal.start_z_big(&c);
+            let mut z_dd = fractal.start_z_double_double(ComplexDD::from_big(&c));
+            let (mut memory, mut memory_dd) = (OrbitMemory::new_big(), OrbitMemory::new_double_double());
+            for _ in 0..10 {
+                fractal.iterate_big(&mut z, &c, &mut memory);
+                fractal.iterate_double_double(&mut z_dd, ComplexDD::from_big(&c), &mut memory_dd);
+            }
+
+            assert!((&z - &z_dd.to_big()).abs_squared() < 1e-50 * z.abs_squared().max(1.));
+        }
+
+        let center = ComplexType::Big(c);
+        let pixel_step = FloatExp::from(1e-20);
+        assert!(matches!(
+            Visualiser::get_needed_center(center.clone(), pixel_step, &Fractal::MagnetI), ComplexType::DoubleDouble(_)
+        ));
+        assert!(matches!(
+            Visualiser::get_needed_center(center.clone(), pixel_step, &Fractal::Custom(Formula::default())), ComplexType::DoubleDouble(_)
+        ));
+        assert!(matches!(
+            Visualiser::get_needed_center(center.clone(), pixel_step, &Fractal::BurningShip), ComplexType::Big(_)
+        ));
+        assert!(matches!(
+            Visualiser::get_needed_center(center, FloatExp::from(1e-40), &Fractal::MagnetI), ComplexType::Big(_)
+        ));
+    }
+
+    #[test]
+    fn precision_follows_zoom() {
+        let shallow = precision_for(FloatExp::parse("1e-20").unwrap());
+        let deep = precision_for(FloatExp::parse("1e-2000").unwrap());
+        assert!(shallow > 66 && shallow < 66 + 2*PRECISION_GUARD_BITS);
+        assert!(deep > 6643 && deep < 6643 + 2*PRECISION_GUARD_BITS);
+
+        // typed digits are kept, even past what the zoom needs
+        let mut center = ComplexType::Double(Complex::new(0., 0.));
+        center.update_real_from_string(format!("-1.{}1", "0".repeat(60)), shallow);
+        match center {
+            ComplexType::Big(c) => {
+                assert!(c.precision() >= shallow);
+                assert!((c.real + FBig::ONE).to_f64().value() < 0.);
+            },
+            _ => panic!("center should be arbitrary precision")
+        }
+    }
+
+    #[test]
+    fn tricorn_lerp() {
+        let halfway = Fractal::lerp_fractal(&Fractal::Mandelbrot, &Fractal::Tricorn, 0.5);
+        let end = Fractal::lerp_fractal(&Fractal::Mandelbrot, &Fractal::Tricorn, 1.);
+
+        assert!(matches!(halfway, Fractal::TricornBlend { seed: None, .. }));
+        assert!(matches!(end, Fractal::Tricorn));
+    }
+
+    #[test]
+    fn newton_converges_to_roots() {
+        let fractal = Fractal::Newton(NewtonPolynomial::default());
+        let mut z = Complex::new(2., 0.5);
+        let mut memory = OrbitMemory::new_double();
+        for _ in 0..50 {
+            fractal.iterate_double(&mut z, Complex::new(2., 0.5), &mut memory);
+        }
+
+        assert_eq!(fractal.get_roots().len(), 3);
+        assert!(fractal.finished_double(z, &memory, BAILOUT) == Some(SetRegion::Converged));
+        assert!(NewtonPolynomial::parse("0,0").is_err());
+
+        // a stationary point stays where it is instead of becoming NaN
+        let mut stationary = Complex::new(0., 0.);
+        fractal.iterate_double(&mut stationary, Complex::new(0., 0.), &mut memory);
+        assert!(stationary.real == 0. && stationary.im == 0.);
+    }
+
+    #[test]
+    fn phoenix_lerp() {
+        let phoenix1 = Fractal::Phoenix { p: JuliaSeed::new(-0.5, 0.) };
+        let phoenix2 = Fractal::Phoenix { p: JuliaSeed::new(0.5, 1.) };
+        let halfway = Fractal::lerp_fractal(&phoenix1, &phoenix2, 0.5);
+
+        assert_eq!(halfway.get_export_string(), "Phoenix(0,0.5)");
+    }
+
+    #[test]
+    fn magnet_converges_separately() {
+        let c = Complex::new(-1., 0.);
+        let mut z = Fractal::MagnetI.start_z_double(c);
+        let mut memory = OrbitMemory::new_double();
+        for _ in 0..10 {
+            Fractal::MagnetI.iterate_double(&mut z, c, &mut memory);
+        }
+
+        assert!(Fractal::MagnetI.finished_double(z, &memory, 4.) == Some(SetRegion::Converged));
+        assert!(Fractal::MagnetI.finished_double(Complex::new(3., 0.), &memory, 4.) == Some(SetRegion::OutSet));
+        assert!(layers::LayerRange::Converged.layer_applies(SetRegion::Converged));
+        assert!(!layers::LayerRange::OutSet.layer_applies(SetRegion::Converged));
+
+        // stationary points stay where they are instead of becoming NaN
+        let nova = Fractal::Nova { poly: NewtonPolynomial::default(), relaxation: 1. };
+        let mut stationary = Complex::new(0., 0.);
+        nova.iterate_double(&mut stationary, Complex::new(0.5, 0.), &mut memory);
+        assert!(stationary.real == 0. && stationary.im == 0.);
+    }
+
+    #[test]
+    fn fractional_powers_stay_double() {
+        let center = ComplexType::Double(Complex::new(-0.5, 0.1));
+        let pixel_step = FloatExp::from(1e-20);
+        let needed = |fractal: Fractal| Visualiser::get_needed_center(center.clone(), pixel_step, &fractal);
+
+        assert!(matches!(needed(Fractal::Multibrot { power: 2.5 }), ComplexType::Double(_)));
+        assert!(matches!(needed(Fractal::Multibrot { power: 3. }), ComplexType::DoubleDouble(_)));
+        assert!(Fractal::Hybrid(Hybrid::parse("Mandelbrot; Multibrot(1.5)").unwrap()).has_fractional_power());
+    }
+
+    #[test]
+    fn fractal_export() {
+        let fractal = Fractal::MultiJulia { power: 3.5, seed: JuliaSeed::new(-0.4, 0.6) };
+        let imported = Fractal::import_from_str(&fractal.get_export_string());
+
+        assert_eq!(fractal.get_export_string(), imported.get_export_string());
+        assert!(matches!(Fractal::import_from_str("Celtic"), Fractal::Celtic));
+        assert_eq!(Fractal::import_from_str("Phoenix(-0.5,0.25)").get_export_string(), "Phoenix(-0.5,0.25)");
+        assert_eq!(Fractal::import_from_str("Nova(0.5;1,0,0,-1)").get_export_string(), "Nova(0.5;1,0,0,-1)");
+        assert_eq!(Fractal::import_from_str("Custom(z^3 + c*sin(z))").get_export_string(), "Custom(z^3 + c*sin(z))");
+    }
+
+    #[test]
+    fn fractal_equality() {
+        let multibrot = Fractal::Multibrot { power: 3. };
+        assert!(multibrot != Fractal::Mandelbrot && multibrot.same_family(&Fractal::Mandelbrot));
+        assert!(multibrot == Fractal::Multibrot { power: 3. });
+
+        let julia = Fractal::Julia(JuliaSeed::new(-0.4, 0.6));
+        assert!(julia != Fractal::Julia(JuliaSeed::new(0., 0.)));
+        // the drop down's variants have default parameters
+        assert!(julia.same_variant(&Fractal::Julia(JuliaSeed::new(0., 0.))));
+        assert!(!julia.same_variant(&Fractal::BurningShip));
+    }
+
+    #[test]
+    fn lyapunov_exponent() {
+        let fractal = Fractal::Lyapunov(LyapunovSequence::parse("aabab").unwrap());
+        let exponent = |c: Complex| {
+            let mut z = fractal.start_z_double(c);
+            let mut memory = OrbitMemory::new_double();
+            for _ in 0..LYAPUNOV_WARMUP+1000 {
+                fractal.iterate_double(&mut z, c, &mut memory);
+            }
+            z.im / 1000.
+        };
+
+        assert!((exponent(Complex::new(2.5, 2.5)) - 0.5f64.ln()).abs() < 1e-6);
+        assert!(exponent(Complex::new(4., 4.)) > 0.);
+        assert_eq!(fractal.get_export_string(), "Lyapunov(AABAB)");
+        assert!(LyapunovSequence::parse("ABC").is_err());
+        assert!(LyapunovSequence::parse("").is_err());
+    }
+
+    #[test]
+    fn hybrid_steps() {
+        let hybrid = Hybrid::parse("Mandelbrot*2; Multibrot(3); Phoenix(-0.5,0)*2").unwrap();
+
+        assert!(matches!(hybrid.step(1), Fractal::Mandelbrot));
+        assert!(matches!(hybrid.step(2), Fractal::Multibrot { .. }));
+        assert!(matches!(hybrid.step(4), Fractal::Phoenix { .. }));
+        assert!(matches!(hybrid.step(5), Fractal::Mandelbrot));
+        assert!(!Fractal::Hybrid(hybrid).can_perturb());
+        assert!(Fractal::Hybrid(Hybrid::default()).can_perturb());
+
+        // rebasing stays in phase with the formula the pixel is on
+        let default = Fractal::Hybrid(Hybrid::default());
+        let mut memory = OrbitMemory::new_double();
+        memory.iteration = 1;
+        assert_eq!(default.rebase_iteration(&memory, 100), Some(1));
+        assert_eq!(default.rebase_iteration(&memory, 1), None);
+        assert_eq!(Fractal::Mandelbrot.rebase_iteration(&memory, 1), Some(0));
+        assert_eq!(
+            Fractal::import_from_str("Hybrid(Mandelbrot*2; BurningShip*1)").get_export_string(), 
+            "Hybrid(Mandelbrot*2; BurningShip*1)"
+        );
+        assert!(Hybrid::parse("").is_err());
+        assert!(Hybrid::parse("Mandelbrot*0").is_err());
+        assert!(Hybrid::parse("Newton(1,0,-1)").is_err());
+    }
 }
\ No newline at end of file
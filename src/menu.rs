@@ -6,14 +6,15 @@
 
 use macroquad::prelude::*;
 
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, atomic::{AtomicUsize, Ordering}};
 use native_dialog::FileDialog;
 use clipboard::ClipboardProvider;
 
 use super::{
-    ScreenDimensions, Visualiser, interpolate_colour, 
-    Fractal, JuliaSeed,
+    ScreenDimensions, Visualiser, RenderPass, interpolate_colour, precision_for,
+    Fractal, JuliaSeed, NewtonPolynomial, Formula, Hybrid, LyapunovSequence,
     complex::{ComplexType, Complex},
+    floatexp::FloatExp,
     layers::*,
     orbit_trap::*,
     palettes::*,
@@ -231,7 +232,7 @@ fn get_back_gradient(visualiser: &Visualiser, start_x: u16, width: u16, height:
         let mut colour = None;
         for layer in visualiser.layers.layers.iter() {
             // has to be a colour layer out of the set
-            if layer.layer_type.shading_layer() || !layer.layer_range.layer_applies(false) {
+            if layer.layer_type.shading_layer() || !layer.layer_range.layer_applies(SetRegion::OutSet) {
                 continue
             }
             let layer_colour = layer.palette.get_colour_at_percentage(percent, false);
@@ -2032,6 +2033,10 @@ impl Slider {
 pub trait DropDownType<T> {
     fn get_variants() -> Vec<T>;
     fn get_string(&self) -> String;
+    /// whether this is shown as the given variant
+    fn same_variant(&self, variant: &T) -> bool where Self: PartialEq<T> {
+        self == variant
+    }
 }
 
 #[derive(Clone)]
@@ -2137,7 +2142,7 @@ impl<T: DropDownType<T> + std::cmp::PartialEq + Clone> DropDown<T> {
             return None;
         } 
 
-        let non_current: Vec<&T> = self.variants.iter().filter(|x| **x != *current_variant).collect();
+        let non_current: Vec<&T> = self.variants.iter().filter(|x| !current_variant.same_variant(x)).collect();
         Some(non_current[self.hover_index-1].clone())
     }
 
@@ -2184,7 +2189,7 @@ impl<T: DropDownType<T> + std::cmp::PartialEq + Clone> DropDown<T> {
         );
 
         self.open_grad_input_box.draw_gradient();
-        let non_current: Vec<&T> = self.variants.iter().filter(|x| **x != *current_variant).collect();
+        let non_current: Vec<&T> = self.variants.iter().filter(|x| !current_variant.same_variant(x)).collect();
         for i in 0..non_current.len() {
             let mut container = self.open_grad_input_box.inner_rect().clone();
             let index_y_add = i as f32 * ( self.closed_grad_input_box.outer_rect().h - self.closed_grad_input_box.border_size());
@@ -2423,7 +2428,7 @@ struct JuliaEditor {
     request_render: bool,
     rendering_image: Arc<Mutex<Image>>,
     pixel_step: f64,
-    progress_tracker: Arc<Mutex<usize>>,
+    progress_tracker: Arc<AtomicUsize>,
     saved_julia_seed: Complex,
     prev_julia_seed: Complex
 }
@@ -2456,7 +2461,7 @@ impl JuliaEditor {
             request_render: true,
             rendering_image: Arc::new(Mutex::new(Image::empty())),
             pixel_step: 3.5 / select_rect.w as f64,
-            progress_tracker: Arc::new(Mutex::new(0)),
+            progress_tracker: Arc::new(AtomicUsize::new(0)),
             saved_julia_seed: Complex::new(0., 0.),
             prev_julia_seed: Complex::new(0., 0.)
         }
@@ -2492,28 +2497,30 @@ impl JuliaEditor {
         
         self.process_click(visualiser);
 
-        if self.request_render && self.progress_tracker.lock().unwrap().clone() == 0 {
+        if self.request_render && self.progress_tracker.load(Ordering::Relaxed) == 0 {
             self.rendering_image = Arc::new(Mutex::new(Image::gen_image_color(
                 self.dims.x as u16, self.dims.y as u16, WHITE
             )));
-            self.progress_tracker = Arc::new(Mutex::new(0));
+            self.progress_tracker = Arc::new(AtomicUsize::new(0));
             visualiser.generate_given_image(
                 Arc::clone(&self.rendering_image), 
+                None,
                 self.dims.clone(), 
-                Some(Fractal::Mandelbrot),
-                self.pixel_step, 
+                Some(visualiser.fractal.parameter_plane()),
+                FloatExp::from(self.pixel_step), 
                 Some(ComplexType::Double(Complex::new(-0.5, 0.))),
                 1,
+                RenderPass::Full,
                 Arc::clone(&self.progress_tracker),
                 false
             );
             self.request_render = false;
         }
 
-        if self.progress_tracker.lock().unwrap().clone() <= self.dims.numpixels() {
+        if self.progress_tracker.load(Ordering::Relaxed) <= self.dims.numpixels() {
             Texture2D::delete(&self.select_image);
             self.select_image = Texture2D::from_image(&self.rendering_image.lock().unwrap().clone());
-            self.progress_tracker = Arc::new(Mutex::new(0));
+            self.progress_tracker = Arc::new(AtomicUsize::new(0));
         }
     }
 
@@ -2560,7 +2567,7 @@ impl JuliaEditor {
         self.request_render = true;
 
         self.saved_julia_seed = match &visualiser.fractal {
-            Fractal::Julia(seed) => seed.double.clone(),
+            Fractal::Julia(seed) | Fractal::MultiJulia { seed, .. } => seed.double.clone(),
             _ => panic!("fractal wasn't julia")
         };
     }
@@ -2571,11 +2578,28 @@ impl JuliaEditor {
 }
 
 struct GeneralMenu {
+    fractal: DropDown<Fractal>,
     center_re: TextBox,
     center_im: TextBox,
     magnification: TextBox,
     max_iterations: TextBox,
     bailout: TextBox,
+    power: TextBox,
+    /// newton/nova polynomial coefficients, shown in place of the power
+    coefficients: TextBox,
+    /// nova relaxation, shown in place of the julia editor
+    relaxation: TextBox,
+    /// custom formula, shown in place of the julia editor
+    formula: TextBox,
+    /// hybrid formula sequence, shown in place of the julia editor
+    hybrid: TextBox,
+    /// lyapunov A/B sequence, shown in place of the julia editor
+    lyapunov: TextBox,
+    /// why the last entered formula or sequence couldn't be used
+    formula_error: InputLabel,
+    /// phoenix parameter, shown in place of the julia editor
+    phoenix_re: TextBox,
+    phoenix_im: TextBox,
     julia_editor: JuliaEditor,
     progress_bar: ProgressBar
 }
@@ -2584,24 +2608,67 @@ impl GeneralMenu {
         let font = main_font().await;
         let box_vert_padding = screen_height() * DEFAULT_INPUT_BOX_VERT_PADDING;
 
-        let center_re_input_box = GradientInputBox::default_top(visualiser);
+        let fractal_input_box = GradientInputBox::default_top(visualiser);
+        let center_re_input_box = fractal_input_box.next_vert(visualiser, box_vert_padding, true);
         let center_im_input_box = center_re_input_box.next_vert(visualiser, box_vert_padding, true);
         let magnification_input_box = center_im_input_box.next_vert(visualiser, box_vert_padding, true);
         let max_iter_input_box = magnification_input_box.next_vert(visualiser, box_vert_padding, true);
         let bailout_input_box = max_iter_input_box.next_vert(visualiser, box_vert_padding, true);
+        let power_input_box = bailout_input_box.next_vert(visualiser, box_vert_padding, true);
 
-        let seed_re_input_box = bailout_input_box.next_vert(visualiser, box_vert_padding, true);
+        let seed_re_input_box = power_input_box.next_vert(visualiser, box_vert_padding, true);
+        let coefficients_input_box = power_input_box.sealed_clone(visualiser);
+        let phoenix_re_input_box = seed_re_input_box.sealed_clone(visualiser);
+        let relaxation_input_box = seed_re_input_box.sealed_clone(visualiser);
+        let formula_input_box = seed_re_input_box.sealed_clone(visualiser);
+        let hybrid_input_box = seed_re_input_box.sealed_clone(visualiser);
+        let lyapunov_input_box = seed_re_input_box.sealed_clone(visualiser);
+        let phoenix_im_input_box = phoenix_re_input_box.next_vert(visualiser, box_vert_padding, true);
 
         let input_boxes = vec![
-            center_re_input_box, center_im_input_box, magnification_input_box, max_iter_input_box, bailout_input_box
+            center_re_input_box, center_im_input_box, magnification_input_box, max_iter_input_box, bailout_input_box,
+            power_input_box
         ];
 
         GeneralMenu { 
+            fractal: DropDown::new(visualiser, fractal_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "fractal", true), 
+                InputLabel::default_input_box_content(font)).await,
             center_re: GeneralMenu::create_textbox(visualiser, &input_boxes, font, 0),
             center_im: GeneralMenu::create_textbox(visualiser, &input_boxes, font, 1),
             magnification: GeneralMenu::create_textbox(visualiser, &input_boxes, font, 2),
             max_iterations: GeneralMenu::create_textbox(visualiser, &input_boxes, font, 3),
             bailout: GeneralMenu::create_textbox(visualiser, &input_boxes, font, 4),
+            power: GeneralMenu::create_textbox(visualiser, &input_boxes, font, 5),
+            coefficients: TextBox::new(coefficients_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "coefficients", true), 
+                InputLabel::default_input_box_content(font), ""),
+            relaxation: TextBox::new(relaxation_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "relaxation", true), 
+                InputLabel::default_input_box_content(font), ""),
+            formula: TextBox::new(formula_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "formula", true), 
+                InputLabel::default_input_box_content(font), ""),
+            hybrid: TextBox::new(hybrid_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "sequence", true), 
+                InputLabel::default_input_box_content(font), ""),
+            lyapunov: TextBox::new(lyapunov_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "sequence", true), 
+                InputLabel::default_input_box_content(font), ""),
+            formula_error: InputLabel::new(
+                "", 
+                font, 
+                screen_width() * DEFAULT_INPUT_BOX_LABEL_FONT_PROPORTION,
+                RED, false,
+                screen_width() * DEFAULT_INPUT_BOX_LABEL_PADDING,
+                TextAlign::Bottom(false)
+            ),
+            phoenix_re: TextBox::new(phoenix_re_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "p (re)", true), 
+                InputLabel::default_input_box_content(font), ""),
+            phoenix_im: TextBox::new(phoenix_im_input_box, 
+                InputLabel::default_input_box_label(visualiser, font, "p (im)", true), 
+                InputLabel::default_input_box_content(font), ""),
             julia_editor: JuliaEditor::new(visualiser, seed_re_input_box).await,
             progress_bar: ProgressBar::new(
                 visualiser, 
@@ -2631,16 +2698,22 @@ impl GeneralMenu {
         TextBox::new(
             input_boxes[i].clone(),
             InputLabel::default_input_box_label(visualiser, font, 
-                ["center (re)", "center (im)", "magnification", "max iterations", "bailout"][i], 
+                ["center (re)", "center (im)", "magnification", "max iterations", "bailout", "power"][i], 
                 true),
             InputLabel::default_input_box_content(font),
             ""
         )
     }
 
-    fn all_text_boxes(&mut self) -> [&mut TextBox; 5] {
+    /// returns the text boxes currently in use
+    /// (the coefficients replace the power for polynomial fractals)
+    fn all_text_boxes(&mut self, polynomial: bool) -> [&mut TextBox; 6] {
         [
-            &mut self.center_re, &mut self.center_im, &mut self.magnification, &mut self.max_iterations, &mut self.bailout
+            &mut self.center_re, &mut self.center_im, &mut self.magnification, &mut self.max_iterations, &mut self.bailout,
+            match polynomial {
+                true => &mut self.coefficients,
+                false => &mut self.power
+            }
         ]
     }
 
@@ -2653,19 +2726,24 @@ impl GeneralMenu {
             visualiser.get_magnification().to_string()
         } else if i == 3 {
             (visualiser.max_iterations as u32).to_string()
-        } else {
+        } else if i == 4 {
             visualiser.bailout2.sqrt().to_string()
+        } else {
+            match &visualiser.fractal {
+                Fractal::Newton(poly) | Fractal::Nova { poly, .. } => poly.get_export_string(),
+                fractal => fractal.get_power().to_string()
+            }
         }
     }
 
     fn update_data(visualiser: &mut Visualiser, i: usize, new: String) {
         if i == 0 {
-            visualiser.center.update_real_from_string(new);
+            visualiser.center.update_real_from_string(new, precision_for(visualiser.pixel_step));
         } else if i == 1 {
-            visualiser.center.update_im_from_string(new);
+            visualiser.center.update_im_from_string(new, precision_for(visualiser.pixel_step));
         } else if i == 2 {
-            if let Ok(new) = new.parse::<f64>() {
-                if new <= 0.0 { return };
+            if let Ok(new) = FloatExp::parse(&new) {
+                if new <= FloatExp::ZERO { return };
                 visualiser.set_pixel_step(0.005/new);
             }
         } else if i == 3 {
@@ -2673,29 +2751,182 @@ impl GeneralMenu {
                 if new < 1 { return };
                 visualiser.max_iterations = new as f32;
             }
-        } else {
+        } else if i == 4 {
             if let Ok(new) = new.parse::<f64>() {
                 if new <= 0.0 { return };
                 visualiser.bailout2 = new.powi(2);
             }
+        } else if let Fractal::Newton(poly) | Fractal::Nova { poly, .. } = &mut visualiser.fractal {
+            if let Ok(new) = NewtonPolynomial::parse(&new) {
+                *poly = new;
+            }
+        } else {
+            if let Ok(new) = new.parse::<f64>() {
+                if new <= 1.0 { return };
+                visualiser.fractal.set_power(new);
+            }
+        }
+    }
+
+    /// draws and updates the phoenix parameter boxes
+    /// (only when the fractal is a phoenix)
+    fn update_phoenix(&mut self, visualiser: &mut Visualiser) {
+        let p = match &mut visualiser.fractal {
+            Fractal::Phoenix { p } => p,
+            _ => return
+        };
+        if self.fractal.open {
+            self.phoenix_re.draw();
+            self.phoenix_im.draw();
+            return;
+        }
+
+        let mut changed_p = false;
+        if let Some(Ok(new)) = self.phoenix_re.update(p.double.real.to_string())
+                                   .map(|r| r.parse::<f64>()) {
+            p.set_real(new);
+            changed_p = true;
+        }
+        if let Some(Ok(new)) = self.phoenix_im.update(p.double.im.to_string())
+                                   .map(|i| i.parse::<f64>()) {
+            p.set_im(new);
+            changed_p = true;
+        }
+
+        if changed_p {
+            visualiser.generate_image();
+        }
+    }
+
+    /// draws and updates the custom formula box, showing any parse errors below it
+    /// (only when the fractal is custom)
+    fn update_custom(&mut self, visualiser: &mut Visualiser) {
+        let formula = match &mut visualiser.fractal {
+            Fractal::Custom(formula) => formula,
+            _ => return
+        };
+        if self.fractal.open {
+            self.formula.draw();
+        } else if let Some(new) = self.formula.update(formula.get_text().to_string()) {
+            match Formula::parse(&new) {
+                Err(e) => self.formula_error.change_text(e),
+                Ok(new) => {
+                    *formula = new;
+                    self.formula_error.change_text("");
+                    visualiser.generate_image();
+                }
+            }
+        }
+
+        self.formula_error.draw(&self.formula.grad_input_box.input_box);
+    }
+
+    /// draws and updates the hybrid sequence box, showing any parse errors below it
+    /// (only when the fractal is a hybrid)
+    fn update_hybrid(&mut self, visualiser: &mut Visualiser) {
+        let hybrid = match &mut visualiser.fractal {
+            Fractal::Hybrid(hybrid) => hybrid,
+            _ => return
+        };
+        if self.fractal.open {
+            self.hybrid.draw();
+        } else if let Some(new) = self.hybrid.update(hybrid.get_export_string()) {
+            match Hybrid::parse(&new) {
+                Err(e) => self.formula_error.change_text(e),
+                Ok(new) => {
+                    *hybrid = new;
+                    self.formula_error.change_text("");
+                    visualiser.generate_image();
+                }
+            }
+        }
+
+        self.formula_error.draw(&self.hybrid.grad_input_box.input_box);
+    }
+
+    /// draws and updates the lyapunov sequence box, showing any parse errors below it
+    /// (only when the fractal is a lyapunov)
+    fn update_lyapunov(&mut self, visualiser: &mut Visualiser) {
+        let sequence = match &mut visualiser.fractal {
+            Fractal::Lyapunov(sequence) => sequence,
+            _ => return
+        };
+        if self.fractal.open {
+            self.lyapunov.draw();
+        } else if let Some(new) = self.lyapunov.update(sequence.get_export_string()) {
+            match LyapunovSequence::parse(&new) {
+                Err(e) => self.formula_error.change_text(e),
+                Ok(new) => {
+                    *sequence = new;
+                    self.formula_error.change_text("");
+                    visualiser.generate_image();
+                }
+            }
+        }
+
+        self.formula_error.draw(&self.lyapunov.grad_input_box.input_box);
+    }
+
+    /// draws and updates the nova relaxation box
+    /// (only when the fractal is a nova)
+    fn update_nova(&mut self, visualiser: &mut Visualiser) {
+        let relaxation = match &mut visualiser.fractal {
+            Fractal::Nova { relaxation, .. } => relaxation,
+            _ => return
+        };
+        if self.fractal.open {
+            self.relaxation.draw();
+            return;
+        }
+
+        if let Some(Ok(new)) = self.relaxation.update(relaxation.to_string())
+                                   .map(|r| r.parse::<f64>()) {
+            *relaxation = new;
+            visualiser.generate_image();
         }
     }
 }
 impl MenuType for GeneralMenu {
     fn update(&mut self, visualiser: &mut Visualiser) -> MenuSignal {
-        for (i, text_box) in self.all_text_boxes().iter_mut().enumerate() {
-            let output = text_box.update(GeneralMenu::get_data(visualiser, i));
-            if let Some(new) = output {
-                GeneralMenu::update_data(visualiser, i, new);
-                visualiser.generate_image();
+        let polynomial = matches!(visualiser.fractal, Fractal::Newton(_) | Fractal::Nova { .. });
+        if self.fractal.open {
+            for text_box in self.all_text_boxes(polynomial).iter() {
+                text_box.draw();
+            }
+        } else {
+            let mut changed_power = false;
+            for (i, text_box) in self.all_text_boxes(polynomial).iter_mut().enumerate() {
+                let output = text_box.update(GeneralMenu::get_data(visualiser, i));
+                if let Some(new) = output {
+                    GeneralMenu::update_data(visualiser, i, new);
+                    visualiser.generate_image();
+                    changed_power = changed_power || i == 5;
+                }
+            }
+            // julia editor shows the mandelbrot plane for the current power
+            if changed_power {
+                self.julia_editor.request_render = true;
             }
         }
 
         self.julia_editor.update(visualiser);
+        self.update_phoenix(visualiser);
+        self.update_nova(visualiser);
+        self.update_custom(visualiser);
+        self.update_hybrid(visualiser);
+        self.update_lyapunov(visualiser);
+
+        if let Some(mut new) = self.fractal.update(&visualiser.fractal) {
+            new.set_power(visualiser.fractal.get_power());
+            self.formula_error.change_text("");
+            visualiser.center = ComplexType::Double(new.default_center());
+            visualiser.set_fractal(new);
+            self.julia_editor.request_render = true;
+        }
 
         if visualiser.rendering {
             self.progress_bar.draw(
-                visualiser.progress_tracker.lock().unwrap().clone() as f32 / 
+                visualiser.progress_tracker.load(Ordering::Relaxed) as f32 / 
                     visualiser.current_dimensions.numpixels() as f32,
                 true, true
             );
@@ -2707,16 +2938,25 @@ impl MenuType for GeneralMenu {
     }
 
     fn get_editing(&mut self) -> bool {
-        for text_box in self.all_text_boxes().iter() {
+        for text_box in self.all_text_boxes(false).iter() {
             if text_box.selected { return true }
         }
-        self.julia_editor.get_editing()
+        self.coefficients.selected || self.relaxation.selected || self.formula.selected || self.hybrid.selected || self.lyapunov.selected || self.phoenix_re.selected || self.phoenix_im.selected ||
+            self.julia_editor.get_editing()
     }
 
     fn refresh_gradients(&mut self, visualiser: &Visualiser) {
-        for text_box in self.all_text_boxes().iter_mut() {
+        self.fractal.refresh_gradient(visualiser);
+        for text_box in self.all_text_boxes(false).iter_mut() {
             text_box.refresh_gradient(visualiser);
         }
+        self.coefficients.refresh_gradient(visualiser);
+        self.relaxation.refresh_gradient(visualiser);
+        self.formula.refresh_gradient(visualiser);
+        self.hybrid.refresh_gradient(visualiser);
+        self.lyapunov.refresh_gradient(visualiser);
+        self.phoenix_re.refresh_gradient(visualiser);
+        self.phoenix_im.refresh_gradient(visualiser);
         self.julia_editor.refresh_gradients(visualiser);
         self.progress_bar.refresh_gradient(visualiser);
     }
@@ -3433,6 +3673,8 @@ impl MenuType for LayersMenu {
 
         // iterate and updated the layer managers
         let mut changed = false;
+        // the range and strength only change how points are coloured
+        let mut recoloured = false;
         let mut drag_i: Option<usize> = None;
         let mut released_i: Option<usize> = None;
         let mut delete_i: Option<usize> = None;
@@ -3442,7 +3684,7 @@ impl MenuType for LayersMenu {
                 i, 
                 !inactive_dropdowns.contains(&i)
             );
-            if this_changed { changed = true }
+            if this_changed { recoloured = true }
 
             if manager.dragging {
                 drag_i = Some(i);
@@ -3495,9 +3737,12 @@ impl MenuType for LayersMenu {
             self.update_scroll(&visualiser, false);
         }
 
-        if changed {
+        if changed || recoloured {
             Layers::place_constraints(&mut visualiser.layers.layers);
-            visualiser.generate_image();
+            match changed {
+                true => visualiser.generate_image(),
+                false => visualiser.recolour_image()
+            }
             return MenuSignal::RefreshGradients;
         }
 
@@ -4364,7 +4609,7 @@ impl MenuType for PaletteEditor {
 
         if changed_this_frame {
             visualiser.layers.layers[self.layer_index].palette.generate_palette(visualiser.max_iterations);
-            visualiser.generate_image();
+            visualiser.recolour_image();
             self.refresh_gradients(visualiser);
         }
 
@@ -4377,7 +4622,7 @@ impl MenuType for PaletteEditor {
         if self.cancel_button.clicked {
             visualiser.layers.layers[self.layer_index].palette = self.old_palette.clone();
             visualiser.layers.layers[self.layer_index].palette.generate_palette(visualiser.max_iterations);
-            visualiser.generate_image();
+            visualiser.recolour_image();
             return MenuSignal::RefreshGradients;
         }
 
@@ -4639,7 +4884,7 @@ impl MenuType for ScreenshotMenu {
             self.draw_top_menu();
 
             self.progress_bar.draw(
-                visualiser.exporter.progress_tracker.lock().unwrap().clone() as f32 / 
+                visualiser.exporter.progress_tracker.load(Ordering::Relaxed) as f32 / 
                     visualiser.exporter.dims.numpixels() as f32,
                 true, true
             );
@@ -5570,7 +5815,10 @@ impl LeaveMenu {
             self.mandelbrot.update();
             if self.mandelbrot.clicked {
                 visualiser.center = ComplexType::Double(Complex::new(-0.5, 0.));
-                visualiser.set_fractal(Fractal::Mandelbrot);
+                visualiser.set_fractal(match visualiser.fractal.is_julia() {
+                    true => visualiser.fractal.parameter_plane(),
+                    false => Fractal::Mandelbrot
+                });
                 self.open = false;
             }
         }
@@ -5583,7 +5831,7 @@ impl LeaveMenu {
             self.julia.update();
             if self.julia.clicked {
                 visualiser.center = ComplexType::Double(Complex::new(0., 0.));
-                visualiser.set_fractal(Fractal::Julia(JuliaSeed::new(0., 0.)));
+                visualiser.set_fractal(visualiser.fractal.julia_plane(JuliaSeed::new(0., 0.)));
                 self.open = false;
             }
         }